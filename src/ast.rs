@@ -0,0 +1,446 @@
+//! Typed syntax tree, built once from the parsed pairs so the checker and every backend
+//! don't each have to destructure them.
+
+mod builder;
+pub mod json;
+
+use serde::{Serialize, Serializer};
+use strum_macros::{Display, IntoStaticStr};
+
+use crate::trivia::{Comment, Trivia};
+
+pub use builder::{build_cfg, build_expr, build_statement, parse_expr_at, parse_program};
+
+/// A range of the source code, in byte offsets, with the 1-based line and column of its start
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl From<pest::Span<'_>> for Span {
+    fn from(span: pest::Span) -> Self {
+        let (line, column) = span.start_pos().line_col();
+
+        Span {
+            start: span.start(),
+            end: span.end(),
+            line,
+            column,
+        }
+    }
+}
+
+impl Span {
+    /// Span from the start of `self` to the end of `other`
+    pub fn to(self, other: Span) -> Span {
+        Span {
+            end: other.end,
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Program {
+    /// `#[key=value]` sections on top of the script
+    pub cfg: Vec<CfgEntry>,
+    pub statements: Vec<Statement>,
+    /// Comments after the last statement
+    pub dangling: Vec<Comment>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CfgEntry {
+    pub key: String,
+    /// Value without the surrounding quotes
+    pub value: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Statement {
+    #[serde(flatten)]
+    pub kind: StatementKind,
+    pub span: Span,
+    pub trivia: Trivia,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "node", rename_all = "camelCase")]
+pub enum StatementKind {
+    /// `var name = value;`, or `const name = value;` with `constant`
+    VarDeclaration {
+        name: Identifier,
+        value: Expr,
+        constant: bool,
+    },
+    VarAssignment {
+        name: Identifier,
+        value: Expr,
+    },
+    Expr(Expr),
+    /// Content of an `unparsed ###{ ... }###` block, kept verbatim
+    Escape(String),
+}
+
+impl StatementKind {
+    /// The expression of the statement, or the value it assigns
+    pub fn expr(&self) -> Option<&Expr> {
+        match self {
+            StatementKind::VarDeclaration { value, .. }
+            | StatementKind::VarAssignment { value, .. }
+            | StatementKind::Expr(value) => Some(value),
+            StatementKind::Escape(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Identifier {
+    pub name: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Expr {
+    #[serde(flatten)]
+    pub kind: ExprKind,
+    pub span: Span,
+    /// Type of the expression, set by the checker
+    #[serde(rename = "type")]
+    pub ty: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "node", rename_all = "camelCase")]
+pub enum ExprKind {
+    Literal(Literal),
+    /// `"Hello ${name}"`, a string with expressions put into it
+    Interpolation(Vec<StringPart>),
+    Variable(Identifier),
+    /// `ByOption.ID`, optionally prefixed with its package
+    EnumMember {
+        package: Option<String>,
+        name: Identifier,
+        member: Identifier,
+    },
+    /// `#:NavigateToUrl(...)`. Calls without a package refer to local functions.
+    Call {
+        package: Option<String>,
+        name: Identifier,
+        args: Vec<Expr>,
+        /// Name of the builtin function the call resolves to, set by the checker. Calls of a
+        /// deprecated alias resolve to its replacement, with `args` rewritten to match it.
+        builtin: Option<&'static str>,
+    },
+    /// `value.click()`, including calls chained on a previous call result
+    MethodCall {
+        receiver: Box<Expr>,
+        name: Identifier,
+        args: Vec<Expr>,
+    },
+    Unary {
+        op: UnaryOp,
+        operand: Box<Expr>,
+    },
+    Binary {
+        op: BinaryOp,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
+    /// `value[index]`
+    Index {
+        target: Box<Expr>,
+        index: Box<Expr>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Literal {
+    /// Content between the quotes, with its escape sequences as written
+    String(String),
+    /// Integer digits as written. Negative numbers are a negation of the literal.
+    Number(String),
+    /// Decimal or scientific number as written, e.g. `12.50` or `1e3`
+    Decimal(String),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, IntoStaticStr)]
+pub enum BinaryOp {
+    #[strum(serialize = "+")]
+    Add,
+    #[strum(serialize = "-")]
+    Sub,
+    #[strum(serialize = "/")]
+    Div,
+    #[strum(serialize = "*")]
+    Mul,
+    #[strum(serialize = "%")]
+    Mod,
+    #[strum(serialize = "^")]
+    Pow,
+    #[strum(serialize = "==")]
+    Eq,
+    #[strum(serialize = "!=")]
+    Ne,
+    #[strum(serialize = "<")]
+    Lt,
+    #[strum(serialize = "<=")]
+    Le,
+    #[strum(serialize = ">")]
+    Gt,
+    #[strum(serialize = ">=")]
+    Ge,
+    #[strum(serialize = "&&")]
+    And,
+    #[strum(serialize = "||")]
+    Or,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "node", rename_all = "camelCase")]
+pub enum StringPart {
+    /// Text with its escape sequences as written
+    Text(String),
+    Expr(Expr),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+pub enum UnaryOp {
+    #[strum(serialize = "-")]
+    Neg,
+    #[strum(serialize = "!")]
+    Not,
+}
+
+impl Serialize for BinaryOp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Serialize for UnaryOp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Assoc {
+    Left,
+    Right,
+}
+
+/// How tightly the operators of a language bind, higher binds tighter
+pub struct OperatorTable {
+    pub binary: fn(BinaryOp) -> (u8, Assoc),
+    pub unary: fn(UnaryOp) -> u8,
+}
+
+/// Operator precedence of Autalon itself
+pub const AUTALON_OPERATORS: OperatorTable = OperatorTable {
+    binary: |op| match op {
+        BinaryOp::Or => (1, Assoc::Left),
+        BinaryOp::And => (2, Assoc::Left),
+        BinaryOp::Eq | BinaryOp::Ne => (3, Assoc::Left),
+        BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => (4, Assoc::Left),
+        BinaryOp::Add | BinaryOp::Sub => (5, Assoc::Left),
+        BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => (6, Assoc::Left),
+        // -2 ^ 2 is -(2 ^ 2)
+        BinaryOp::Pow => (8, Assoc::Right),
+    },
+    unary: |_| 7,
+};
+
+impl OperatorTable {
+    /// Whether `operand` has to be put in parentheses when it's an operand of `parent`.
+    /// `side` is the side of a binary `parent` the operand is on.
+    pub fn needs_parens(&self, parent: &ExprKind, operand: &Expr, side: Assoc) -> bool {
+        let (parent_precedence, parent_assoc) = match parent {
+            ExprKind::Binary { op, .. } => (self.binary)(*op),
+            ExprKind::Unary { op, .. } => {
+                // Keep `- -a` from turning into `--a`
+                if matches!(operand.kind, ExprKind::Unary { .. }) {
+                    return true;
+                }
+                ((self.unary)(*op), side)
+            }
+            _ => return false,
+        };
+
+        let precedence = match &operand.kind {
+            ExprKind::Binary { op, .. } => (self.binary)(*op).0,
+            ExprKind::Unary { op, .. } => (self.unary)(*op),
+            _ => return false,
+        };
+
+        precedence < parent_precedence || (precedence == parent_precedence && side != parent_assoc)
+    }
+}
+
+impl BinaryOp {
+    pub fn symbol(self) -> &'static str {
+        self.into()
+    }
+
+    pub fn is_arithmetic(self) -> bool {
+        matches!(
+            self,
+            BinaryOp::Add
+                | BinaryOp::Sub
+                | BinaryOp::Div
+                | BinaryOp::Mul
+                | BinaryOp::Mod
+                | BinaryOp::Pow
+        )
+    }
+
+    pub fn is_comparison(self) -> bool {
+        matches!(
+            self,
+            BinaryOp::Eq | BinaryOp::Ne | BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge
+        )
+    }
+
+    pub fn is_logic(self) -> bool {
+        matches!(self, BinaryOp::And | BinaryOp::Or)
+    }
+}
+
+impl Expr {
+    pub fn new(kind: ExprKind, span: Span) -> Expr {
+        Expr {
+            kind,
+            span,
+            ty: None,
+        }
+    }
+
+    /// Call `f` on the expression and every expression inside it, outermost first
+    pub fn walk<'a>(&'a self, f: &mut dyn FnMut(&'a Expr)) {
+        f(self);
+
+        match &self.kind {
+            ExprKind::Literal(_) | ExprKind::Variable(_) | ExprKind::EnumMember { .. } => (),
+            ExprKind::Interpolation(parts) => {
+                for part in parts {
+                    if let StringPart::Expr(expr) = part {
+                        expr.walk(f);
+                    }
+                }
+            }
+            ExprKind::Call { args, .. } => args.iter().for_each(|x| x.walk(f)),
+            ExprKind::MethodCall { receiver, args, .. } => {
+                receiver.walk(f);
+                args.iter().for_each(|x| x.walk(f));
+            }
+            ExprKind::Unary { operand, .. } => operand.walk(f),
+            ExprKind::Binary { lhs, rhs, .. } => {
+                lhs.walk(f);
+                rhs.walk(f);
+            }
+            ExprKind::Index { target, index } => {
+                target.walk(f);
+                index.walk(f);
+            }
+        }
+    }
+
+    /// Like [`Expr::walk`], with mutable access
+    pub fn walk_mut(&mut self, f: &mut dyn FnMut(&mut Expr)) {
+        f(self);
+
+        match &mut self.kind {
+            ExprKind::Literal(_) | ExprKind::Variable(_) | ExprKind::EnumMember { .. } => (),
+            ExprKind::Interpolation(parts) => {
+                for part in parts {
+                    if let StringPart::Expr(expr) = part {
+                        expr.walk_mut(f);
+                    }
+                }
+            }
+            ExprKind::Call { args, .. } => args.iter_mut().for_each(|x| x.walk_mut(f)),
+            ExprKind::MethodCall { receiver, args, .. } => {
+                receiver.walk_mut(f);
+                args.iter_mut().for_each(|x| x.walk_mut(f));
+            }
+            ExprKind::Unary { operand, .. } => operand.walk_mut(f),
+            ExprKind::Binary { lhs, rhs, .. } => {
+                lhs.walk_mut(f);
+                rhs.walk_mut(f);
+            }
+            ExprKind::Index { target, index } => {
+                target.walk_mut(f);
+                index.walk_mut(f);
+            }
+        }
+    }
+
+    /// Whether methods can be called on the expression without putting it in parentheses
+    pub fn is_receiver(&self) -> bool {
+        matches!(
+            self.kind,
+            ExprKind::Literal(_)
+                | ExprKind::Interpolation(_)
+                | ExprKind::Variable(_)
+                | ExprKind::Call { .. }
+                | ExprKind::MethodCall { .. }
+        )
+    }
+}
+
+/// Value of string text, with its escape sequences resolved
+pub fn unescape(text: &str) -> String {
+    let mut res = String::new();
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('b') => res.push('\u{8}'),
+            Some('f') => res.push('\u{c}'),
+            Some('n') => res.push('\n'),
+            Some('r') => res.push('\r'),
+            Some('t') => res.push('\t'),
+            Some('u') => {
+                let code = chars.by_ref().take(4).collect::<String>();
+                let code = u32::from_str_radix(&code, 16).unwrap_or(0xfffd);
+
+                // Characters outside of the basic plane are written as surrogate pairs
+                let code = match code {
+                    0xd800..=0xdbff if chars.as_str().starts_with("\\u") => {
+                        let low = chars
+                            .as_str()
+                            .get(2..6)
+                            .and_then(|x| u32::from_str_radix(x, 16).ok());
+
+                        match low {
+                            Some(low @ 0xdc00..=0xdfff) => {
+                                chars.nth(5);
+                                0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00)
+                            }
+                            _ => code,
+                        }
+                    }
+                    code => code,
+                };
+                res.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+            // \", \\, \/ and \$
+            Some(c) => res.push(c),
+            None => res.push('\\'),
+        }
+    }
+
+    res
+}
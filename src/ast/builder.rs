@@ -0,0 +1,421 @@
+use color_eyre::eyre::{bail, eyre, ContextCompat, Report};
+use pest::{
+    iterators::{Pair, Pairs},
+    pratt_parser::{Assoc, Op, PrattParser},
+    Parser,
+};
+
+use crate::{
+    autalonparser::{AutalonParser, Rule},
+    checker::funcs::unwrap_inner,
+    trivia::{self, Trivia},
+};
+
+use super::{
+    BinaryOp, CfgEntry, Expr, ExprKind, Identifier, Literal, Program, Span, Statement,
+    StatementKind, StringPart, UnaryOp,
+};
+
+/// Parse a whole script into its syntax tree, with comments attached to the statements
+#[tracing::instrument(skip_all)]
+pub fn parse_program(code: &str) -> Result<Program, Report> {
+    let parsed =
+        AutalonParser::parse(Rule::program, code).map_err(|err| eyre!("Failed to parse\n{err}"))?;
+
+    let cfg = parsed
+        .clone()
+        .filter(|x| x.as_rule() == Rule::cfg_section)
+        .map(build_cfg)
+        .collect::<Result<Vec<CfgEntry>, Report>>()?;
+
+    let annotated = trivia::annotate(code, parsed);
+    let statements = annotated
+        .statements
+        .into_iter()
+        .map(|x| build_statement(x.pair, x.trivia))
+        .collect::<Result<Vec<Statement>, Report>>()?;
+
+    Ok(Program {
+        cfg,
+        statements,
+        dangling: annotated.dangling,
+    })
+}
+
+/// Parse an expression that isn't written in the script, like a default argument. Every node of
+/// it gets `span`, the place of the script it's put in.
+pub fn parse_expr_at(code: &str, span: Span) -> Result<Expr, Report> {
+    let mut parsed = AutalonParser::parse(Rule::expr, code)
+        .map_err(|err| eyre!("Failed to parse '{code}'\n{err}"))?;
+    let mut expr = build_expr(parsed.next().context("Can't get expression")?)?;
+
+    expr.walk_mut(&mut |x| {
+        x.span = span;
+        match &mut x.kind {
+            ExprKind::Variable(name)
+            | ExprKind::Call { name, .. }
+            | ExprKind::MethodCall { name, .. } => name.span = span,
+            ExprKind::EnumMember { name, member, .. } => {
+                name.span = span;
+                member.span = span;
+            }
+            _ => (),
+        }
+    });
+
+    Ok(expr)
+}
+
+/// Build a cfg entry from a `cfg_section` pair
+pub fn build_cfg(pair: Pair<Rule>) -> Result<CfgEntry, Report> {
+    let span = pair.as_span().into();
+    let mut assign_pair = unwrap_inner(pair)?.into_inner();
+
+    let key = assign_pair.next().context("Can't get cfg key")?;
+    let value = assign_pair.next().context("Can't get cfg value")?;
+
+    Ok(CfgEntry {
+        key: key.as_str().to_string(),
+        value: value.as_str().trim_matches('"').to_string(),
+        span,
+    })
+}
+
+/// Build a statement from a `statement` pair
+#[tracing::instrument(skip_all)]
+pub fn build_statement(pair: Pair<Rule>, trivia: Trivia) -> Result<Statement, Report> {
+    let span = pair.as_span().into();
+    let inner = unwrap_inner(pair)?;
+
+    let kind = match inner.as_rule() {
+        Rule::expr => StatementKind::Expr(build_expr(inner)?),
+        Rule::var_declaration | Rule::const_declaration => {
+            let constant = inner.as_rule() == Rule::const_declaration;
+            let (name, value) = build_assignment(unwrap_inner(inner)?)?;
+            StatementKind::VarDeclaration {
+                name,
+                value,
+                constant,
+            }
+        }
+        Rule::var_assignment => {
+            let (name, value) = build_assignment(inner)?;
+            StatementKind::VarAssignment { name, value }
+        }
+        Rule::escape_block => {
+            let text = inner.as_str();
+            let content_start = text.find("###{").map_or(0, |x| x + "###{".len());
+            let content_end = text.rfind("}###").unwrap_or(text.len());
+
+            StatementKind::Escape(text[content_start..content_end].to_string())
+        }
+        nonmatch => bail!("{nonmatch:?} is not a statement!"),
+    };
+
+    Ok(Statement { kind, span, trivia })
+}
+
+fn build_assignment(pair: Pair<Rule>) -> Result<(Identifier, Expr), Report> {
+    let mut inner = pair.into_inner();
+
+    let identifier = inner.next().context("Can't get variable identifier")?;
+    let expr = inner.next().context("Can't get variable expression")?;
+
+    Ok((build_identifier(&identifier), build_expr(expr)?))
+}
+
+fn build_identifier(pair: &Pair<Rule>) -> Identifier {
+    Identifier {
+        name: pair.as_str().to_string(),
+        span: pair.as_span().into(),
+    }
+}
+
+fn binary_op(rule: Rule) -> Result<BinaryOp, Report> {
+    Ok(match rule {
+        Rule::add_op => BinaryOp::Add,
+        Rule::sub_op => BinaryOp::Sub,
+        Rule::div_op => BinaryOp::Div,
+        Rule::mul_op => BinaryOp::Mul,
+        Rule::mod_op => BinaryOp::Mod,
+        Rule::pow_op => BinaryOp::Pow,
+        Rule::eq_op => BinaryOp::Eq,
+        Rule::ne_op => BinaryOp::Ne,
+        Rule::lt_op => BinaryOp::Lt,
+        Rule::le_op => BinaryOp::Le,
+        Rule::gt_op => BinaryOp::Gt,
+        Rule::ge_op => BinaryOp::Ge,
+        Rule::and_op => BinaryOp::And,
+        Rule::or_op => BinaryOp::Or,
+        nonmatch => bail!("{nonmatch:?} is not an operator!"),
+    })
+}
+
+fn build_operators(pairs: Pairs<Rule>) -> Result<Expr, Report> {
+    // Lowest precedence first, see `AUTALON_OPERATORS`
+    let parser = PrattParser::new()
+        .op(Op::infix(Rule::or_op, Assoc::Left))
+        .op(Op::infix(Rule::and_op, Assoc::Left))
+        .op(Op::infix(Rule::eq_op, Assoc::Left) | Op::infix(Rule::ne_op, Assoc::Left))
+        .op(Op::infix(Rule::lt_op, Assoc::Left)
+            | Op::infix(Rule::le_op, Assoc::Left)
+            | Op::infix(Rule::gt_op, Assoc::Left)
+            | Op::infix(Rule::ge_op, Assoc::Left))
+        .op(Op::infix(Rule::add_op, Assoc::Left) | Op::infix(Rule::sub_op, Assoc::Left))
+        .op(Op::infix(Rule::div_op, Assoc::Left)
+            | Op::infix(Rule::mul_op, Assoc::Left)
+            | Op::infix(Rule::mod_op, Assoc::Left))
+        .op(Op::prefix(Rule::neg_op) | Op::prefix(Rule::not_logic))
+        .op(Op::infix(Rule::pow_op, Assoc::Right));
+
+    parser
+        .map_primary(build_expr)
+        .map_prefix(|op, operand| {
+            let operand = operand?;
+
+            let span = Span::from(op.as_span()).to(operand.span);
+            let op = match op.as_rule() {
+                Rule::neg_op => UnaryOp::Neg,
+                Rule::not_logic => UnaryOp::Not,
+                nonmatch => bail!("{nonmatch:?} is not an operator!"),
+            };
+
+            Ok(Expr::new(
+                ExprKind::Unary {
+                    op,
+                    operand: Box::new(operand),
+                },
+                span,
+            ))
+        })
+        .map_infix(|lhs, op, rhs| {
+            // Bubble up any errors from left/right side
+            let lhs = lhs?;
+            let rhs = rhs?;
+
+            let span = lhs.span.to(rhs.span);
+            Ok(Expr::new(
+                ExprKind::Binary {
+                    op: binary_op(op.as_rule())?,
+                    lhs: Box::new(lhs),
+                    rhs: Box::new(rhs),
+                },
+                span,
+            ))
+        })
+        .parse(pairs)
+}
+
+/// Build an expression from any expression pair, e.g. `expr`, `string` or `function_call`
+#[tracing::instrument(skip_all)]
+pub fn build_expr(pair: Pair<Rule>) -> Result<Expr, Report> {
+    let span = pair.as_span().into();
+
+    match pair.as_rule() {
+        Rule::expr => build_operators(pair.into_inner()),
+        // Parentheses only group, the spans of the grouped expression include them
+        Rule::paren_expr => {
+            let paren_span = pair.as_span();
+            let mut inner = pair.into_inner().peekable();
+
+            let expr = inner.next().context("Can't get grouped expression")?;
+            let span = first_span(paren_span, inner.peek());
+            let expr = Expr {
+                span,
+                ..build_expr(expr)?
+            };
+
+            build_method_chain(expr, inner)
+        }
+        // Methods called right on a literal, e.g. `"12".toNumber()`
+        Rule::literal_call => {
+            let mut inner = pair.into_inner();
+            let literal = inner.next().context("Can't get literal")?;
+
+            build_method_chain(build_expr(literal)?, inner)
+        }
+
+        Rule::string => {
+            if !pair
+                .clone()
+                .into_inner()
+                .any(|x| x.as_rule() == Rule::interpolation)
+            {
+                let text = pair.as_str();
+                return Ok(Expr::new(
+                    ExprKind::Literal(Literal::String(text[1..text.len() - 1].to_string())),
+                    span,
+                ));
+            }
+
+            let parts = pair
+                .into_inner()
+                .map(|part| match part.as_rule() {
+                    Rule::interpolation => Ok(StringPart::Expr(build_expr(unwrap_inner(part)?)?)),
+                    _ => Ok(StringPart::Text(part.as_str().to_string())),
+                })
+                .collect::<Result<Vec<StringPart>, Report>>()?;
+
+            Ok(Expr::new(ExprKind::Interpolation(parts), span))
+        }
+        Rule::number => {
+            let text = pair.as_str().to_string();
+            let literal = if text.contains(['.', 'e', 'E']) {
+                Literal::Decimal(text)
+            } else {
+                Literal::Number(text)
+            };
+
+            Ok(Expr::new(ExprKind::Literal(literal), span))
+        }
+        Rule::bool => Ok(Expr::new(
+            ExprKind::Literal(Literal::Bool(pair.as_str() == "true")),
+            span,
+        )),
+
+        Rule::member_access => build_member_access(pair),
+        Rule::function_call => build_call(pair),
+        Rule::array_access => {
+            let mut inner = pair.into_inner();
+            let target = inner.next().context("Can't get accessed value")?;
+            let index = inner.next().context("Can't get index")?;
+
+            Ok(Expr::new(
+                ExprKind::Index {
+                    target: Box::new(build_expr(target)?),
+                    index: Box::new(build_expr(index)?),
+                },
+                span,
+            ))
+        }
+
+        nonmatch => Err(eyre!("{nonmatch:?} is not an expression!")),
+    }
+}
+
+/// Split a `member_access` into its package name and identifiers
+fn member_tokens(pair: Pair<Rule>) -> Result<(Option<String>, Vec<Identifier>), Report> {
+    let mut package = None;
+    let mut identifiers = vec![];
+
+    for token in pair.into_inner() {
+        match token.as_rule() {
+            Rule::package => package = Some(unwrap_inner(token)?.as_str().to_string()),
+            _ => identifiers.push(build_identifier(&token)),
+        }
+    }
+
+    Ok((package, identifiers))
+}
+
+fn build_member_access(pair: Pair<Rule>) -> Result<Expr, Report> {
+    let span = pair.as_span().into();
+
+    let kind = match member_tokens(pair)? {
+        (None, mut identifiers) if identifiers.len() == 1 => {
+            ExprKind::Variable(identifiers.remove(0))
+        }
+        (Some(_), identifiers) if identifiers.len() == 1 => {
+            bail!("Package name other than builtin ('#') is currently not supported")
+        }
+        (package, mut identifiers) if identifiers.len() == 2 => {
+            let member = identifiers.remove(1);
+            let name = identifiers.remove(0);
+
+            ExprKind::EnumMember {
+                package,
+                name,
+                member,
+            }
+        }
+        (_, identifiers) => bail!(
+            "Invalid member access token length! Expected token count: 1 or 2; Received token count: {}",
+            identifiers.len()
+        ),
+    };
+
+    Ok(Expr::new(kind, span))
+}
+
+fn build_args(pair: Option<Pair<Rule>>) -> Result<Vec<Expr>, Report> {
+    match pair {
+        None => Ok(vec![]),
+        Some(outer_pair) => outer_pair
+            .into_inner()
+            .map(build_expr)
+            .collect::<Result<Vec<Expr>, Report>>(),
+    }
+}
+
+fn build_call(pair: Pair<Rule>) -> Result<Expr, Report> {
+    let call_span = pair.as_span();
+    let mut inner = pair.into_inner().peekable();
+
+    let member = inner.next().context("Can't get member or function name!")?;
+    let args = build_args(inner.next_if(|x| x.as_rule() == Rule::comma_separated_expr))?;
+
+    let span = first_span(call_span, inner.peek());
+
+    let kind = match member_tokens(member)? {
+        (package, mut identifiers) if identifiers.len() == 1 => ExprKind::Call {
+            package,
+            name: identifiers.remove(0),
+            args,
+            builtin: None,
+        },
+        (None, mut identifiers) if identifiers.len() == 2 => {
+            let name = identifiers.remove(1);
+            let value = identifiers.remove(0);
+            let value_span = value.span;
+
+            ExprKind::MethodCall {
+                receiver: Box::new(Expr::new(ExprKind::Variable(value), value_span)),
+                name,
+                args,
+            }
+        }
+        (Some(_), identifiers) if identifiers.len() == 2 => {
+            bail!("Builtin value is currently unsupported for now")
+        }
+        (_, identifiers) => bail!(
+            "Invalid function token length! Expected token count: 1 or 2; Received token count: {}",
+            identifiers.len()
+        ),
+    };
+
+    build_method_chain(Expr::new(kind, span), inner)
+}
+
+/// Span of what comes before the chained method calls, the first of which is `next`
+fn first_span(span: pest::Span, next: Option<&Pair<Rule>>) -> Span {
+    let end = next.map_or(span.end(), |x| x.as_span().start());
+
+    span.get(..end - span.start())
+        .map_or(span.into(), Into::into)
+}
+
+/// Chained calls operate on whatever the previous call returned
+fn build_method_chain<'a>(
+    mut expr: Expr,
+    method_calls: impl Iterator<Item = Pair<'a, Rule>>,
+) -> Result<Expr, Report> {
+    for method_call in method_calls {
+        let method_span = method_call.as_span();
+        let mut method_inner = method_call.into_inner();
+
+        let name = method_inner.next().context("Can't get method name!")?;
+        let args = build_args(method_inner.next())?;
+
+        let span = expr.span.to(method_span.into());
+        expr = Expr::new(
+            ExprKind::MethodCall {
+                receiver: Box::new(expr),
+                name: build_identifier(&name),
+                args,
+            },
+            span,
+        );
+    }
+
+    Ok(expr)
+}
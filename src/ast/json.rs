@@ -0,0 +1,65 @@
+//! Machine readable view of a script, for tooling that shouldn't re-implement the grammar.
+//!
+//! The schema is versioned by [`SCHEMA_VERSION`], bumped on every breaking change. Version 2:
+//!
+//! - The top level object holds `schemaVersion`, `program` and `diagnostics`.
+//! - `program` holds `cfg` (`key`, `value` and `span` of each `#[key=value]`), `statements`,
+//!   and `dangling`, the comments after the last statement.
+//! - Statements and expressions are tagged with `kind`, and hold their fields in `node`.
+//!   Both have a `span` with the byte `start` and `end`, and the 1-based `line` and `column`.
+//! - Statement kinds are `varDeclaration` (`name`, `value`, and `constant` for `const`
+//!   declarations), `varAssignment` (`name`, `value`), `expr` and `escape` (the verbatim
+//!   content). Statements carry their comments in `trivia`.
+//! - Expression kinds are `literal` (`{"string": ...}`, `{"number": ...}` for integers,
+//!   `{"decimal": ...}` or `{"bool": ...}`, strings and numbers as written), `interpolation`
+//!   (a list of `text` and `expr` parts),
+//!   `variable`, `enumMember` (`package`, `name`, `member`), `call` (`package`, `name`, `args`,
+//!   and `builtin`, the resolved builtin function), `methodCall` (`receiver`, `name`, `args`),
+//!   `unary` (`op`, `operand`), `binary` (`op`, `lhs`, `rhs`) and `index` (`target`, `index`).
+//!   Parentheses only group, they don't have a node of their own.
+//! - Expressions have the `type` inferred by the checker, or `null` when checking their
+//!   statement failed. Those failures are listed in `diagnostics`, with the warnings.
+//!   Diagnostics may list `related` locations, like the original declaration of a variable,
+//!   and `fixes`, replacements of the `start` to `end` range of the script. Fixes that guess
+//!   what was meant, like the correction of a typo, aren't `preferred`.
+//! - Calls of a deprecated alias keep the name as written. Their `builtin` is the replacement,
+//!   and their `args` are the ones of the replacement.
+//!
+//! Version 1 differed in that:
+//!
+//! - Negative numbers were a `number` literal holding the sign, rather than a `unary` negation.
+//!   There were no `decimal` literals, `interpolation` or `unary` expressions, and no
+//!   `constant` declarations.
+//! - Every statement was checked on its own, so the variables declared by the statements
+//!   before it were unknown. `diagnostics` only listed errors, without `code`, `related`
+//!   locations or `fixes`.
+
+use color_eyre::eyre::Report;
+use serde::Serialize;
+
+use crate::checker::{self, diagnostic::Diagnostic};
+
+use super::{parse_program, Program};
+
+pub const SCHEMA_VERSION: u32 = 2;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramJson {
+    pub schema_version: u32,
+    pub program: Program,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Parse and check a script, keeping the statements that fail checking with their problems
+#[tracing::instrument(skip_all)]
+pub fn parse_to_json(code: &str) -> Result<ProgramJson, Report> {
+    let mut program = parse_program(code)?;
+    let diagnostics = checker::check_ast(&mut program);
+
+    Ok(ProgramJson {
+        schema_version: SCHEMA_VERSION,
+        program,
+        diagnostics,
+    })
+}
@@ -0,0 +1,418 @@
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use autalon_transpiler::{
+    checker::diagnostic::Diagnostic,
+    transpiler::{TranspileOptions, TranspilerOption},
+};
+use clap::{Parser, Subcommand};
+use color_eyre::eyre::{bail, Context, Report};
+
+use crate::input::{check_unique_stems, read_inputs, Input};
+
+/// Exit code when any of the scripts has an error
+const EXIT_DIAGNOSTICS: u8 = 1;
+/// Exit code when the command itself failed, e.g. an unreadable file
+const EXIT_FAILURE: u8 = 2;
+
+#[derive(Parser)]
+#[command(
+    name = "autalon",
+    version,
+    about = "Check and transpile Autalon scripts"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check scripts for errors without transpiling them
+    Check {
+        /// Script files or glob patterns. Use `-` to read from stdin
+        #[arg(required = true)]
+        inputs: Vec<String>,
+    },
+    /// Transpile scripts into the target language
+    Transpile {
+        /// Language to transpile into
+        #[arg(short, long, default_value = "groovy")]
+        target: TranspilerOption,
+        /// Output file, or output directory when transpiling multiple scripts. Defaults to stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Put a `// atln:N` comment after every statement, pointing at its line in the script
+        #[arg(long)]
+        line_markers: bool,
+        /// Write a `.map.json` source map next to every output file
+        #[arg(long, requires = "output")]
+        source_map: bool,
+        /// Script files or glob patterns. Use `-` to read from stdin
+        #[arg(required = true)]
+        inputs: Vec<String>,
+    },
+    /// Translate the Groovy locations of a stack trace back into script locations
+    Trace {
+        /// Source map written by `transpile --source-map`
+        source_map: PathBuf,
+        /// Only translate locations in this generated file, e.g. `Script1665.groovy`
+        #[arg(long)]
+        generated_file: Option<String>,
+        /// File holding the stack trace. Defaults to stdin
+        trace: Option<PathBuf>,
+    },
+    /// Print the builtin function metadata as JSON
+    Metadata {
+        /// Output file. Defaults to stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Watch a directory, re-transpiling changed scripts into a Katalon project
+    Watch {
+        /// Directory containing the scripts
+        source_dir: PathBuf,
+        /// Katalon project directory to write the `Scripts/` and `Test Cases/` into
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Format scripts in place, or print them when read from stdin
+    Fmt {
+        /// Only check the formatting, listing the scripts that would change
+        #[arg(long)]
+        check: bool,
+        /// Script files or glob patterns. Use `-` to read from stdin
+        #[arg(required = true)]
+        inputs: Vec<String>,
+    },
+    /// Migrate scripts to the current version in place, printing the diff of every change
+    Fix {
+        /// Only print the diffs, without writing the scripts
+        #[arg(long)]
+        dry_run: bool,
+        /// Script files or glob patterns. Use `-` to read from stdin
+        #[arg(required = true)]
+        inputs: Vec<String>,
+    },
+    /// Start the language server, communicating over stdio
+    Lsp,
+    /// Transpile scripts and zip them into a Katalon project layout
+    Package {
+        /// Output zip file
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Script files or glob patterns. Use `-` to read from stdin
+        #[arg(required = true)]
+        inputs: Vec<String>,
+    },
+}
+
+pub fn run() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Check { inputs } => check(&inputs),
+        Command::Transpile {
+            target,
+            output,
+            line_markers,
+            source_map,
+            inputs,
+        } => transpile(&inputs, target, output, line_markers, source_map),
+        Command::Trace {
+            source_map,
+            generated_file,
+            trace,
+        } => self::trace(&source_map, generated_file.as_deref(), trace.as_deref()),
+        Command::Metadata { output } => metadata(output),
+        Command::Watch { source_dir, output } => watch(&source_dir, &output),
+        Command::Fmt { check, inputs } => fmt(&inputs, check),
+        Command::Fix { dry_run, inputs } => fix(&inputs, dry_run),
+        Command::Lsp => crate::lsp::run().map(|_| true),
+        Command::Package { output, inputs } => package(&inputs, output),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(EXIT_DIAGNOSTICS),
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            ExitCode::from(EXIT_FAILURE)
+        }
+    }
+}
+
+/// Print the diagnostics of a script, returning whether the script is free of errors
+fn report_diagnostics(input: &Input, diagnostics: &[Diagnostic]) -> bool {
+    print_diagnostics(&input.display_name(), diagnostics);
+
+    !diagnostics.iter().any(Diagnostic::is_error)
+}
+
+fn print_diagnostics(display_name: &str, diagnostics: &[Diagnostic]) {
+    for diagnostic in diagnostics {
+        let code = diagnostic
+            .code
+            .map(|x| format!(" [{x}]"))
+            .unwrap_or_default();
+        eprintln!(
+            "{}:{}:{}: {}: {}{}",
+            display_name,
+            diagnostic.line,
+            diagnostic.column,
+            diagnostic.severity,
+            diagnostic.message,
+            code
+        );
+
+        for related in &diagnostic.related {
+            eprintln!(
+                "{}:{}:{}: note: {}",
+                display_name, related.line, related.column, related.message
+            );
+        }
+        for fix in &diagnostic.fixes {
+            eprintln!("  help: {}: `{}`", fix.title, fix.replacement);
+        }
+    }
+}
+
+fn write_output(output: Option<&PathBuf>, content: &[u8]) -> Result<(), Report> {
+    match output {
+        Some(path) => std::fs::write(path, content)
+            .with_context(|| format!("Failed to write '{}'", path.display())),
+        None => {
+            use std::io::Write;
+            std::io::stdout()
+                .write_all(content)
+                .context("Failed to write to stdout")
+        }
+    }
+}
+
+fn file_extension(target: TranspilerOption) -> &'static str {
+    match target {
+        TranspilerOption::Groovy => "groovy",
+    }
+}
+
+fn check(patterns: &[String]) -> Result<bool, Report> {
+    let inputs = read_inputs(patterns)?;
+
+    let mut passed = true;
+    for input in &inputs {
+        let diagnostics = autalon_transpiler::check(&input.code);
+        passed &= report_diagnostics(input, &diagnostics);
+    }
+
+    Ok(passed)
+}
+
+fn transpile(
+    patterns: &[String],
+    target: TranspilerOption,
+    output: Option<PathBuf>,
+    line_markers: bool,
+    source_map: bool,
+) -> Result<bool, Report> {
+    let inputs = read_inputs(patterns)?;
+
+    // Multiple scripts can't share a single file, so the output is treated as a directory
+    let output_dir = match (&output, inputs.len()) {
+        (_, 0 | 1) => None,
+        (Some(dir), _) => {
+            check_unique_stems(&inputs)?;
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create '{}'", dir.display()))?;
+            Some(dir)
+        }
+        (None, _) => bail!("Transpiling multiple scripts requires an output directory (-o)"),
+    };
+
+    let mut passed = true;
+    for input in &inputs {
+        if !report_diagnostics(input, &autalon_transpiler::check(&input.code)) {
+            passed = false;
+            continue;
+        }
+
+        let options = TranspileOptions {
+            file_name: input.file_name(),
+            line_markers,
+        };
+        let transpiled = autalon_transpiler::transpile(&input.code, target, &options)
+            .with_context(|| format!("Failed to transpile '{}'", input.display_name()))?;
+
+        let path = match output_dir {
+            Some(dir) => Some(
+                dir.join(input.stem())
+                    .with_extension(file_extension(target)),
+            ),
+            None => output.to_owned(),
+        };
+        write_output(path.as_ref(), transpiled.code.as_bytes())?;
+
+        if let (true, Some(path)) = (source_map, path) {
+            let mut map_path = path.into_os_string();
+            map_path.push(".map.json");
+
+            let map = serde_json::to_string_pretty(&transpiled.source_map)?;
+            write_output(Some(&PathBuf::from(map_path)), map.as_bytes())?;
+        }
+    }
+
+    Ok(passed)
+}
+
+fn trace(
+    source_map: &Path,
+    generated_file: Option<&str>,
+    trace: Option<&Path>,
+) -> Result<bool, Report> {
+    use autalon_transpiler::transpiler::source_map::SourceMap;
+
+    let source_map: SourceMap = serde_json::from_str(
+        &std::fs::read_to_string(source_map)
+            .with_context(|| format!("Failed to read '{}'", source_map.display()))?,
+    )
+    .with_context(|| format!("'{}' is not a source map", source_map.display()))?;
+
+    let trace = match trace {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read '{}'", path.display()))?,
+        None => {
+            use std::io::Read;
+
+            let mut trace = String::new();
+            std::io::stdin()
+                .read_to_string(&mut trace)
+                .context("Failed to read from stdin")?;
+            trace
+        }
+    };
+
+    println!(
+        "{}",
+        source_map.translate_stack_trace(&trace, generated_file)
+    );
+
+    Ok(true)
+}
+
+fn fmt(patterns: &[String], check: bool) -> Result<bool, Report> {
+    let inputs = read_inputs(patterns)?;
+
+    let mut formatted_all = true;
+    for input in &inputs {
+        let formatted = autalon_transpiler::format(&input.code)
+            .with_context(|| format!("Failed to format '{}'", input.display_name()))?;
+
+        if check {
+            if formatted != input.code {
+                eprintln!("{} is not formatted", input.display_name());
+                formatted_all = false;
+            }
+            continue;
+        }
+
+        match &input.path {
+            Some(path) if formatted != input.code => {
+                write_output(Some(path), formatted.as_bytes())?
+            }
+            Some(_) => (),
+            None => write_output(None, formatted.as_bytes())?,
+        }
+    }
+
+    Ok(formatted_all)
+}
+
+fn fix(patterns: &[String], dry_run: bool) -> Result<bool, Report> {
+    let inputs = read_inputs(patterns)?;
+
+    let mut fixed_all = true;
+    for input in &inputs {
+        let name = input.file_name().unwrap_or_else(|| "stdin".to_string());
+        let fixed = autalon_transpiler::fix(&input.code, &name)
+            .with_context(|| format!("Failed to fix '{}'", input.display_name()))?;
+
+        for title in &fixed.applied {
+            eprintln!("{}: {title}", input.display_name());
+        }
+
+        if dry_run {
+            if fixed.code != input.code {
+                print!("{}", fixed.diff);
+                fixed_all = false;
+            }
+            continue;
+        }
+
+        match &input.path {
+            Some(path) if fixed.code != input.code => {
+                print!("{}", fixed.diff);
+                write_output(Some(path), fixed.code.as_bytes())?
+            }
+            Some(_) => (),
+            None => write_output(None, fixed.code.as_bytes())?,
+        }
+    }
+
+    Ok(fixed_all)
+}
+
+fn metadata(output: Option<PathBuf>) -> Result<bool, Report> {
+    let metadata = autalon_transpiler::get_fn_metadata()?;
+    write_output(output.as_ref(), metadata.as_bytes())?;
+
+    Ok(true)
+}
+
+fn watch(source_dir: &Path, output: &Path) -> Result<bool, Report> {
+    use autalon_transpiler::libs::watch::WatchEvent;
+
+    eprintln!("Watching '{}' for changes...", source_dir.display());
+
+    autalon_transpiler::libs::watch::watch(source_dir, output, |event| match event {
+        WatchEvent::Transpiled { source, output } => {
+            eprintln!("{} -> {}", source.display(), output.display())
+        }
+        WatchEvent::Failed {
+            source,
+            diagnostics,
+        } => print_diagnostics(&source.display().to_string(), &diagnostics),
+        WatchEvent::Removed { source } => eprintln!("{} removed", source.display()),
+        WatchEvent::Errored { source, error } => {
+            eprintln!("{}: error: {error:#}", source.display())
+        }
+    })?;
+
+    Ok(true)
+}
+
+fn package(patterns: &[String], output: PathBuf) -> Result<bool, Report> {
+    let inputs = read_inputs(patterns)?;
+    check_unique_stems(&inputs)?;
+
+    let mut passed = true;
+    for input in &inputs {
+        passed &= report_diagnostics(input, &autalon_transpiler::check(&input.code));
+    }
+
+    // Don't produce a partial package
+    if !passed {
+        return Ok(false);
+    }
+
+    let scripts = inputs
+        .iter()
+        .map(|input| (input.stem(), input.code.to_owned()))
+        .collect::<Vec<(String, String)>>();
+
+    let package = autalon_transpiler::package_katalon(&scripts)?;
+    write_output(Some(&output), &package)?;
+
+    Ok(true)
+}
@@ -0,0 +1,103 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{bail, eyre, Context, Report};
+
+/// A script to be processed, read either from a file or stdin
+pub struct Input {
+    /// Path of the script, `None` when read from stdin
+    pub path: Option<PathBuf>,
+    pub code: String,
+}
+
+impl Input {
+    /// Name shown in diagnostics
+    pub fn display_name(&self) -> String {
+        match &self.path {
+            Some(path) => path.display().to_string(),
+            None => "<stdin>".to_string(),
+        }
+    }
+
+    /// Name of the script file, `None` when read from stdin
+    pub fn file_name(&self) -> Option<String> {
+        self.path
+            .as_deref()
+            .and_then(Path::file_name)
+            .map(|x| x.to_string_lossy().to_string())
+    }
+
+    /// Name of the script without its extension, used as the test case name
+    pub fn stem(&self) -> String {
+        self.path
+            .as_deref()
+            .and_then(Path::file_stem)
+            .map(|x| x.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Script".to_string())
+    }
+}
+
+/// Fail when two inputs share a name, as their outputs would overwrite each other
+pub fn check_unique_stems(inputs: &[Input]) -> Result<(), Report> {
+    for (i, input) in inputs.iter().enumerate() {
+        if let Some(other) = inputs[..i].iter().find(|x| x.stem() == input.stem()) {
+            bail!(
+                "'{}' and '{}' would both be written as '{}', rename one of them",
+                other.display_name(),
+                input.display_name(),
+                input.stem()
+            )
+        }
+    }
+
+    Ok(())
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
+/// Expand every file path, glob pattern or `-` (stdin) into inputs
+pub fn read_inputs(patterns: &[String]) -> Result<Vec<Input>, Report> {
+    let mut inputs = vec![];
+
+    for pattern in patterns {
+        if pattern == "-" {
+            let mut code = String::new();
+            std::io::stdin()
+                .read_to_string(&mut code)
+                .context("Failed to read from stdin")?;
+
+            inputs.push(Input { path: None, code });
+            continue;
+        }
+
+        let paths = if is_glob(pattern) {
+            let paths = glob::glob(pattern)
+                .map_err(|err| eyre!("Invalid glob pattern '{pattern}': {err}"))?
+                .collect::<Result<Vec<PathBuf>, glob::GlobError>>()?;
+
+            if paths.is_empty() {
+                bail!("No file matched '{pattern}'")
+            }
+
+            paths
+        } else {
+            vec![PathBuf::from(pattern)]
+        };
+
+        for path in paths {
+            let code = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read '{}'", path.display()))?;
+
+            inputs.push(Input {
+                path: Some(path),
+                code,
+            });
+        }
+    }
+
+    Ok(inputs)
+}
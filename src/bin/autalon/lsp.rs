@@ -0,0 +1,387 @@
+use std::collections::HashMap;
+
+use autalon_transpiler::{
+    checker::diagnostic::{Diagnostic, Severity},
+    editor::{
+        self,
+        completion::{CompletionItem, CompletionKind},
+        SourceRange,
+    },
+};
+use color_eyre::eyre::{eyre, Report};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as NotificationTrait, PublishDiagnostics,
+    },
+    request::{
+        CodeActionRequest, Completion, GotoDefinition, HoverRequest, Request as RequestTrait,
+        SignatureHelpRequest,
+    },
+    CodeActionKind, CodeActionOrCommand, CodeActionParams, CodeActionProviderCapability,
+    CompletionOptions, CompletionParams, CompletionResponse, DiagnosticRelatedInformation,
+    DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, Documentation, GotoDefinitionParams, GotoDefinitionResponse, Hover,
+    HoverContents, HoverParams, HoverProviderCapability, Location, MarkupContent, MarkupKind,
+    NumberOrString, OneOf, ParameterInformation, ParameterLabel, Position,
+    PublishDiagnosticsParams, Range, ServerCapabilities, SignatureHelp, SignatureHelpOptions,
+    SignatureHelpParams, SignatureInformation, TextDocumentPositionParams,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextEdit, Url, WorkspaceEdit,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Open documents, as the editor only sends their content on open and change
+type Documents = HashMap<Url, String>;
+
+/// Convert a byte offset into an LSP position, which counts columns in UTF-16 code units
+fn offset_to_position(text: &str, offset: usize) -> Position {
+    let offset = offset.min(text.len());
+    let line_start = text[..offset].rfind('\n').map_or(0, |x| x + 1);
+
+    Position {
+        line: text[..offset].matches('\n').count() as u32,
+        character: text[line_start..offset].encode_utf16().count() as u32,
+    }
+}
+
+fn position_to_offset(text: &str, position: Position) -> usize {
+    let line_start = match position.line {
+        0 => 0,
+        line => text
+            .match_indices('\n')
+            .nth(line as usize - 1)
+            .map_or(text.len(), |(i, _)| i + 1),
+    };
+
+    let mut utf16_count = 0;
+    for (i, c) in text[line_start..].char_indices() {
+        if utf16_count >= position.character as usize || c == '\n' {
+            return line_start + i;
+        }
+        utf16_count += c.len_utf16();
+    }
+
+    text.len()
+}
+
+fn to_range(text: &str, range: SourceRange) -> Range {
+    Range {
+        start: offset_to_position(text, range.start),
+        end: offset_to_position(text, range.end),
+    }
+}
+
+fn to_lsp_diagnostic(uri: &Url, text: &str, diagnostic: &Diagnostic) -> lsp_types::Diagnostic {
+    lsp_types::Diagnostic {
+        range: to_range(
+            text,
+            SourceRange {
+                start: diagnostic.start,
+                end: diagnostic.end,
+            },
+        ),
+        severity: Some(match diagnostic.severity {
+            Severity::Error => DiagnosticSeverity::ERROR,
+            Severity::Warning => DiagnosticSeverity::WARNING,
+        }),
+        source: Some("autalon".to_string()),
+        code: diagnostic
+            .code
+            .map(|x| NumberOrString::String(x.to_string())),
+        message: diagnostic.message.to_owned(),
+        related_information: (!diagnostic.related.is_empty()).then(|| {
+            diagnostic
+                .related
+                .iter()
+                .map(|x| DiagnosticRelatedInformation {
+                    location: Location {
+                        uri: uri.to_owned(),
+                        range: to_range(
+                            text,
+                            SourceRange {
+                                start: x.start,
+                                end: x.end,
+                            },
+                        ),
+                    },
+                    message: x.message.to_owned(),
+                })
+                .collect()
+        }),
+        ..Default::default()
+    }
+}
+
+fn to_lsp_completion(item: CompletionItem) -> lsp_types::CompletionItem {
+    use lsp_types::CompletionItemKind;
+
+    lsp_types::CompletionItem {
+        label: item.label,
+        kind: Some(match item.kind {
+            CompletionKind::Function => CompletionItemKind::FUNCTION,
+            CompletionKind::Method => CompletionItemKind::METHOD,
+            CompletionKind::Enum => CompletionItemKind::ENUM,
+            CompletionKind::EnumMember => CompletionItemKind::ENUM_MEMBER,
+            CompletionKind::Variable => CompletionItemKind::VARIABLE,
+            CompletionKind::Keyword => CompletionItemKind::KEYWORD,
+        }),
+        detail: Some(item.detail),
+        documentation: Some(markdown(item.documentation)),
+        ..Default::default()
+    }
+}
+
+fn markdown(value: String) -> Documentation {
+    Documentation::MarkupContent(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value,
+    })
+}
+
+/// Document text and cursor offset of a position based request
+fn document_offset<'a>(
+    documents: &'a Documents,
+    params: &TextDocumentPositionParams,
+) -> Result<(&'a str, usize), Report> {
+    let text = documents
+        .get(&params.text_document.uri)
+        .ok_or_else(|| eyre!("Document '{}' isn't open", params.text_document.uri))?;
+
+    Ok((text, position_to_offset(text, params.position)))
+}
+
+fn publish_diagnostics(connection: &Connection, uri: Url, text: &str) -> Result<(), Report> {
+    let params = PublishDiagnosticsParams {
+        diagnostics: autalon_transpiler::check(text)
+            .iter()
+            .map(|x| to_lsp_diagnostic(&uri, text, x))
+            .collect(),
+        uri,
+        version: None,
+    };
+
+    connection
+        .sender
+        .send(Message::Notification(Notification::new(
+            PublishDiagnostics::METHOD.to_string(),
+            params,
+        )))?;
+
+    Ok(())
+}
+
+fn parse_params<P: DeserializeOwned>(params: serde_json::Value) -> Result<P, Report> {
+    Ok(serde_json::from_value(params)?)
+}
+
+fn handle_request(documents: &Documents, request: Request) -> Response {
+    fn respond<R: Serialize>(id: lsp_server::RequestId, result: Result<R, Report>) -> Response {
+        match result {
+            Ok(result) => Response::new_ok(id, result),
+            Err(err) => Response::new_err(id, ErrorCode::InternalError as i32, format!("{err:#}")),
+        }
+    }
+
+    let Request { id, method, params } = request;
+
+    match method.as_str() {
+        Completion::METHOD => respond(
+            id,
+            parse_params::<CompletionParams>(params).and_then(|params| {
+                let (text, offset) = document_offset(documents, &params.text_document_position)?;
+
+                Ok(CompletionResponse::Array(
+                    editor::complete(text, offset)
+                        .into_iter()
+                        .map(to_lsp_completion)
+                        .collect(),
+                ))
+            }),
+        ),
+        HoverRequest::METHOD => respond(
+            id,
+            parse_params::<HoverParams>(params).and_then(|params| {
+                let (text, offset) =
+                    document_offset(documents, &params.text_document_position_params)?;
+
+                Ok(editor::hover(text, offset).map(|hover| Hover {
+                    contents: HoverContents::Markup(MarkupContent {
+                        kind: MarkupKind::Markdown,
+                        value: hover.contents,
+                    }),
+                    range: Some(to_range(text, hover.range)),
+                }))
+            }),
+        ),
+        SignatureHelpRequest::METHOD => respond(
+            id,
+            parse_params::<SignatureHelpParams>(params).and_then(|params| {
+                let (text, offset) =
+                    document_offset(documents, &params.text_document_position_params)?;
+
+                Ok(
+                    editor::signature_help(text, offset).map(|help| SignatureHelp {
+                        signatures: vec![SignatureInformation {
+                            label: help.label,
+                            documentation: Some(Documentation::String(help.documentation)),
+                            parameters: Some(
+                                help.parameters
+                                    .into_iter()
+                                    .map(|x| ParameterInformation {
+                                        label: ParameterLabel::Simple(x.label),
+                                        documentation: Some(markdown(x.documentation)),
+                                    })
+                                    .collect(),
+                            ),
+                            active_parameter: None,
+                        }],
+                        active_signature: Some(0),
+                        active_parameter: Some(help.active_parameter as u32),
+                    }),
+                )
+            }),
+        ),
+        GotoDefinition::METHOD => respond(
+            id,
+            parse_params::<GotoDefinitionParams>(params).and_then(|params| {
+                let position_params = params.text_document_position_params;
+                let (text, offset) = document_offset(documents, &position_params)?;
+
+                Ok(editor::definition(text, offset).map(|range| {
+                    GotoDefinitionResponse::Scalar(Location {
+                        uri: position_params.text_document.uri.to_owned(),
+                        range: to_range(text, range),
+                    })
+                }))
+            }),
+        ),
+        CodeActionRequest::METHOD => respond(
+            id,
+            parse_params::<CodeActionParams>(params).and_then(|params| {
+                let uri = params.text_document.uri;
+                let text = documents
+                    .get(&uri)
+                    .ok_or_else(|| eyre!("Document '{uri}' isn't open"))?;
+                let range = SourceRange {
+                    start: position_to_offset(text, params.range.start),
+                    end: position_to_offset(text, params.range.end),
+                };
+
+                Ok(editor::code_actions(text, range)
+                    .into_iter()
+                    .map(|action| {
+                        let edit = TextEdit {
+                            range: to_range(text, action.range),
+                            new_text: action.replacement,
+                        };
+
+                        CodeActionOrCommand::CodeAction(lsp_types::CodeAction {
+                            title: action.title,
+                            kind: Some(CodeActionKind::QUICKFIX),
+                            diagnostics: Some(vec![to_lsp_diagnostic(
+                                &uri,
+                                text,
+                                &action.diagnostic,
+                            )]),
+                            edit: Some(WorkspaceEdit {
+                                changes: Some(HashMap::from([(uri.to_owned(), vec![edit])])),
+                                ..Default::default()
+                            }),
+                            is_preferred: Some(action.preferred),
+                            ..Default::default()
+                        })
+                    })
+                    .collect::<Vec<CodeActionOrCommand>>())
+            }),
+        ),
+        _ => Response::new_err(
+            id,
+            ErrorCode::MethodNotFound as i32,
+            format!("Method '{method}' is not supported"),
+        ),
+    }
+}
+
+fn handle_notification(
+    connection: &Connection,
+    documents: &mut Documents,
+    notification: Notification,
+) -> Result<(), Report> {
+    match notification.method.as_str() {
+        DidOpenTextDocument::METHOD => {
+            let params: DidOpenTextDocumentParams = parse_params(notification.params)?;
+            let uri = params.text_document.uri;
+
+            publish_diagnostics(connection, uri.to_owned(), &params.text_document.text)?;
+            documents.insert(uri, params.text_document.text);
+        }
+        DidChangeTextDocument::METHOD => {
+            let params: DidChangeTextDocumentParams = parse_params(notification.params)?;
+            let uri = params.text_document.uri;
+
+            // Full sync, so the last change holds the whole document
+            if let Some(change) = params.content_changes.into_iter().last() {
+                publish_diagnostics(connection, uri.to_owned(), &change.text)?;
+                documents.insert(uri, change.text);
+            }
+        }
+        DidCloseTextDocument::METHOD => {
+            let params: DidCloseTextDocumentParams = parse_params(notification.params)?;
+            documents.remove(&params.text_document.uri);
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+/// Run the language server over stdio until the editor shuts it down
+pub fn run() -> Result<(), Report> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![":".to_string(), ".".to_string()]),
+            ..Default::default()
+        }),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        signature_help_provider: Some(SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
+            ..Default::default()
+        }),
+        definition_provider: Some(OneOf::Left(true)),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        ..Default::default()
+    };
+
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    let mut documents = Documents::new();
+
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    break;
+                }
+
+                let response = handle_request(&documents, request);
+                connection.sender.send(Message::Response(response))?;
+            }
+            // Stdout carries the protocol, so errors are logged to stderr
+            Message::Notification(notification) => {
+                let method = notification.method.to_owned();
+                if let Err(err) = handle_notification(&connection, &mut documents, notification) {
+                    eprintln!("error: Failed to handle '{method}': {err:#}");
+                }
+            }
+            Message::Response(_) => (),
+        }
+    }
+
+    io_threads.join()?;
+
+    Ok(())
+}
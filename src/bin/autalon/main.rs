@@ -0,0 +1,15 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod cli;
+#[cfg(not(target_arch = "wasm32"))]
+mod input;
+#[cfg(not(target_arch = "wasm32"))]
+mod lsp;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() -> std::process::ExitCode {
+    cli::run()
+}
+
+// The command line interface is native only, the browser uses the exported wasm functions instead
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -1,6 +1,7 @@
 use serde::Serialize;
 
-use strum_macros::{Display, EnumIter, EnumString};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString, IntoStaticStr};
 
 #[allow(dead_code)]
 enum ArgType {
@@ -10,7 +11,7 @@ enum ArgType {
     ByOption,
 }
 
-#[derive(PartialEq, EnumString, EnumIter, Display)]
+#[derive(Clone, Copy, PartialEq, EnumString, EnumIter, Display, IntoStaticStr)]
 pub enum BuiltinPkgFunctions {
     NavigateToUrl,
     GetElementByString,
@@ -22,6 +23,12 @@ pub enum BuiltinPkgFunctions {
 
     SetWindowDimension,
 
+    // Assertion funcs
+    Assert,
+    VerifyElementText,
+    VerifyElementVisible,
+    VerifyUrlContains,
+
     // ExtUI funcs
     ExtUIGetInputFromLabel,
     ExtUIGetIFrameFromLabel,
@@ -47,6 +54,37 @@ pub enum BuiltinPkgFunctions {
     MUIInputRadioUsingIndexByLabelExact,
 }
 
+/// Old names of builtin functions, still accepted in place of the function replacing them
+#[derive(PartialEq, EnumString, EnumIter, Display)]
+pub enum BuiltinPkgAliases {
+    InputTextboxByLabelExact,
+    ClickElementByText,
+    GetElementByText,
+}
+
+#[derive(PartialEq, EnumString, EnumIter, Display)]
+#[strum(serialize_all = "camelCase")]
+pub enum ElementMethods {
+    Click,
+    SendText,
+    GetText,
+}
+
+/// Conversions between the plain value types
+#[derive(PartialEq, EnumString, EnumIter, Display)]
+#[strum(serialize_all = "camelCase")]
+pub enum ConversionMethods {
+    ToString,
+    ToNumber,
+    ToBool,
+}
+
+#[derive(PartialEq, EnumString, EnumIter, Display)]
+pub enum BuiltinPkgEnums {
+    ByOption,
+    FailureHandling,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FunctionArgsMetadata {
@@ -54,15 +92,68 @@ pub struct FunctionArgsMetadata {
     pub description: &'static str,
     pub arg_type: &'static str,
     pub default_value: &'static str,
+    /// Whether calls may leave the argument out, taking its default value. Only the last
+    /// arguments can be.
+    pub optional: bool,
 }
 
-#[derive(Serialize, Display)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Display, EnumString)]
+#[strum(ascii_case_insensitive)]
 pub enum TargetUI {
     Any,
     Ext,
     MUI,
 }
 
+impl TargetUI {
+    /// Prefix of the names of the functions made for the framework
+    pub fn function_prefix(self) -> &'static str {
+        match self {
+            TargetUI::Any => "",
+            TargetUI::Ext => "ExtUI",
+            TargetUI::MUI => "MUI",
+        }
+    }
+}
+
+/// Function doing the same as `name` in another UI framework,
+/// e.g. `MUIInputDateByLabelExact` for `ExtUIInputDateByLabelExact`
+pub fn equivalent_function(name: &str, from: TargetUI, to: TargetUI) -> Option<&'static str> {
+    let base = name.strip_prefix(from.function_prefix())?;
+
+    BuiltinPkgFunctions::iter()
+        .filter(|x| get_fn_metadata(x).target_ui == to)
+        .map(<&'static str>::from)
+        .find(|x| x.strip_prefix(to.function_prefix()) == Some(base))
+}
+
+/// Versions of the `nfunittestlibrary` driver the generated code can target, set with
+/// `#[driver_ver=...]`. Scripts without it target the latest one.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Default,
+    Serialize,
+    Display,
+    EnumString,
+    EnumIter,
+)]
+pub enum DriverVersion {
+    #[strum(serialize = "0.1.1")]
+    #[serde(rename = "0.1.1")]
+    V0_1_1,
+    /// Adds the React MUI getter and waiting for elements to be interactable
+    #[default]
+    #[strum(serialize = "0.2.0")]
+    #[serde(rename = "0.2.0")]
+    V0_2_0,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FunctionMetadata {
@@ -70,10 +161,41 @@ pub struct FunctionMetadata {
     pub display_name: &'static str,
     pub description: &'static str,
     pub target_ui: TargetUI,
+    /// Oldest driver version the function can be called with
+    pub since_driver: DriverVersion,
     pub args: Vec<FunctionArgsMetadata>,
     pub return_type: &'static str,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AliasMetadata {
+    pub name: &'static str,
+    /// Function called in place of the alias
+    pub replacement: &'static str,
+    /// Version of the transpiler that deprecated the name
+    pub since_version: &'static str,
+    /// Arguments of the replacement. `{argN}` is the Nth argument given to the alias,
+    /// anything else is an expression put in as is.
+    pub args: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnumMemberMetadata {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnumMetadata {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub type_name: &'static str,
+    pub members: Vec<EnumMemberMetadata>,
+}
+
 pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
     match builtin_fn {
         BuiltinPkgFunctions::NavigateToUrl => FunctionMetadata {
@@ -85,9 +207,11 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                 description: "URL to navigate to",
                 arg_type: "string",
                 default_value: "https://www.google.com",
+                optional: false,
             }],
             return_type: "void",
             target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
         },
 
         BuiltinPkgFunctions::GetElementByString => FunctionMetadata {
@@ -100,28 +224,33 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "String to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "By Option",
                     description: "The attribute to match with the string",
                     arg_type: "byoption",
                     default_value: "ByOption.Text",
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Tag",
                     description: "Tag to match for the element",
                     arg_type: "string",
                     default_value: "*",
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Exact Match",
                     description: "Match the whole string",
                     arg_type: "bool",
                     default_value: "true",
+                    optional: false,
                 },
             ],
-            return_type: "void",
+            return_type: "element",
             target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
         },
         BuiltinPkgFunctions::ClickElementByString => FunctionMetadata {
             name: "ClickElementByString",
@@ -133,28 +262,33 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "String to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "By Option",
                     description: "The attribute to match with the string",
                     arg_type: "byoption",
                     default_value: "ByOption.Text",
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Tag",
                     description: "Tag to match for the element",
                     arg_type: "string",
                     default_value: "*",
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Exact Match",
                     description: "Match the whole string",
                     arg_type: "bool",
                     default_value: "true",
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
         },
 
         BuiltinPkgFunctions::SendTextToElementByString => FunctionMetadata {
@@ -167,34 +301,40 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "String to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "String to input",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "By Option",
                     description: "The attribute to match with the string",
                     arg_type: "byoption",
                     default_value: "ByOption.Text",
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Tag",
                     description: "Tag to match for the element",
                     arg_type: "string",
                     default_value: "*",
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Exact Match",
                     description: "Match the whole string",
                     arg_type: "bool",
                     default_value: "true",
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
         },
 
         BuiltinPkgFunctions::ExtUIGetInputFromLabel => FunctionMetadata {
@@ -206,9 +346,11 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                 description: "Text label to find",
                 arg_type: "string",
                 default_value: (""),
+                optional: false,
             }],
-            return_type: "void",
+            return_type: "element",
             target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
         },
         BuiltinPkgFunctions::ExtUIGetIFrameFromLabel => FunctionMetadata {
             name: "ExtUIGetIFrameFromLabel",
@@ -219,9 +361,11 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                 description: "Text label to find",
                 arg_type: "string",
                 default_value: (""),
+                optional: false,
             }],
-            return_type: "void",
+            return_type: "element",
             target_ui: TargetUI::Ext,
+            since_driver: DriverVersion::V0_1_1,
         },
         BuiltinPkgFunctions::ExtUIGetWindowFromLabel => FunctionMetadata {
             name: "ExtUIGetWindowFromLabel",
@@ -232,9 +376,11 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                 description: "Text label to find",
                 arg_type: "string",
                 default_value: (""),
+                optional: false,
             }],
-            return_type: "void",
+            return_type: "element",
             target_ui: TargetUI::Ext,
+            since_driver: DriverVersion::V0_1_1,
         },
         BuiltinPkgFunctions::ExtUIGetGroupFromLabel => FunctionMetadata {
             name: "GetGroupFromLabelExtUI",
@@ -245,9 +391,11 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                 description: "Text label to find",
                 arg_type: "string",
                 default_value: (""),
+                optional: false,
             }],
-            return_type: "void",
+            return_type: "element",
             target_ui: TargetUI::Ext,
+            since_driver: DriverVersion::V0_1_1,
         },
 
         BuiltinPkgFunctions::ExtUIInputDateByLabelExact => FunctionMetadata {
@@ -260,16 +408,19 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "Input to send",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::Ext,
+            since_driver: DriverVersion::V0_1_1,
         },
         BuiltinPkgFunctions::ExtUIInputHtmlByLabelExact => FunctionMetadata {
             name: "ExtUIInputHtmlByLabelExact",
@@ -281,16 +432,19 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "Input to send",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::Ext,
+            since_driver: DriverVersion::V0_1_1,
         },
         BuiltinPkgFunctions::ExtUIInputNumberTextboxByLabelExact => FunctionMetadata {
             name: "ExtUIInputNumberTextboxByLabelExact",
@@ -302,16 +456,19 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
-                    description: "Input to send",
-                    arg_type: "string",
-                    default_value: (""),
+                    description: "Number to send",
+                    arg_type: "decimal",
+                    default_value: ("0"),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::Ext,
+            since_driver: DriverVersion::V0_1_1,
         },
         BuiltinPkgFunctions::ExtUIInputTextboxByLabelExact => FunctionMetadata {
             name: "ExtUIInputTextboxByLabelExact",
@@ -323,16 +480,19 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "Input to send",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::Ext,
+            since_driver: DriverVersion::V0_1_1,
         },
         BuiltinPkgFunctions::ExtUIInputDropdownUsingTextByLabelExact => FunctionMetadata {
             name: "ExtUIInputDropdownUsingTextByLabelExact",
@@ -344,16 +504,19 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "Input to send",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::Ext,
+            since_driver: DriverVersion::V0_1_1,
         },
         BuiltinPkgFunctions::ExtUIInputDropdownUsingIndexByLabelExact => FunctionMetadata {
             name: "ExtUIInputDropdownUsingIndexByLabelExact",
@@ -365,16 +528,19 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "Input to send",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::Ext,
+            since_driver: DriverVersion::V0_1_1,
         },
         BuiltinPkgFunctions::ExtUIInputRadioUsingTextByLabelExact => FunctionMetadata {
             name: "ExtUIInputRadioUsingTextByLabelExact",
@@ -386,16 +552,19 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "Input to send",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::Ext,
+            since_driver: DriverVersion::V0_1_1,
         },
         BuiltinPkgFunctions::ExtUIInputRadioUsingIndexByLabelExact => FunctionMetadata {
             name: "ExtUIInputRadioUsingIndexByLabelExact",
@@ -407,16 +576,19 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "Input to send",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::Ext,
+            since_driver: DriverVersion::V0_1_1,
         },
 
         BuiltinPkgFunctions::GetAndSwitchToAnyIFrame => FunctionMetadata {
@@ -426,6 +598,7 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
             args: vec![],
             return_type: "void",
             target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
         },
         BuiltinPkgFunctions::GetAndSwitchToParentIFrame => FunctionMetadata {
             name: "GetAndSwitchToParentIFrame",
@@ -434,6 +607,7 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
             args: vec![],
             return_type: "void",
             target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
         },
         BuiltinPkgFunctions::GetAndSwitchToRootIFrame => FunctionMetadata {
             name: "GetAndSwitchToRootIFrame",
@@ -442,6 +616,7 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
             args: vec![],
             return_type: "void",
             target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
         },
 
         BuiltinPkgFunctions::SetWindowDimension => FunctionMetadata {
@@ -454,16 +629,122 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Width of the window",
                     arg_type: "number",
                     default_value: "800",
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Height",
                     description: "Height of the window",
                     arg_type: "number",
                     default_value: "600",
+                    optional: false,
+                },
+            ],
+            return_type: "void",
+            target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
+        },
+        BuiltinPkgFunctions::Assert => FunctionMetadata {
+            name: "Assert",
+            display_name: "Assert",
+            description: "Check that the condition is true",
+            args: vec![
+                FunctionArgsMetadata {
+                    display_name: "Condition",
+                    description: "Condition to check",
+                    arg_type: "bool",
+                    default_value: "true",
+                    optional: false,
+                },
+                FunctionArgsMetadata {
+                    display_name: "Failure Handling",
+                    description: "What to do with the test when the check fails",
+                    arg_type: "failurehandling",
+                    default_value: "FailureHandling.Stop",
+                    optional: true,
+                },
+            ],
+            return_type: "void",
+            target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
+        },
+        BuiltinPkgFunctions::VerifyElementText => FunctionMetadata {
+            name: "VerifyElementText",
+            display_name: "Verify Element Text",
+            description: "Check that the element's text matches the expected text",
+            args: vec![
+                FunctionArgsMetadata {
+                    display_name: "Element",
+                    description: "Element to check",
+                    arg_type: "element",
+                    default_value: (""),
+                    optional: false,
+                },
+                FunctionArgsMetadata {
+                    display_name: "Text",
+                    description: "Expected text of the element",
+                    arg_type: "string",
+                    default_value: (""),
+                    optional: false,
+                },
+                FunctionArgsMetadata {
+                    display_name: "Failure Handling",
+                    description: "What to do with the test when the check fails",
+                    arg_type: "failurehandling",
+                    default_value: "FailureHandling.Stop",
+                    optional: true,
+                },
+            ],
+            return_type: "void",
+            target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
+        },
+        BuiltinPkgFunctions::VerifyElementVisible => FunctionMetadata {
+            name: "VerifyElementVisible",
+            display_name: "Verify Element Visible",
+            description: "Check that the element is visible on the page",
+            args: vec![
+                FunctionArgsMetadata {
+                    display_name: "Element",
+                    description: "Element to check",
+                    arg_type: "element",
+                    default_value: (""),
+                    optional: false,
+                },
+                FunctionArgsMetadata {
+                    display_name: "Failure Handling",
+                    description: "What to do with the test when the check fails",
+                    arg_type: "failurehandling",
+                    default_value: "FailureHandling.Stop",
+                    optional: true,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
+        },
+        BuiltinPkgFunctions::VerifyUrlContains => FunctionMetadata {
+            name: "VerifyUrlContains",
+            display_name: "Verify Url Contains",
+            description: "Check that the current page Url contains the text",
+            args: vec![
+                FunctionArgsMetadata {
+                    display_name: "Text",
+                    description: "Text the Url should contain",
+                    arg_type: "string",
+                    default_value: (""),
+                    optional: false,
+                },
+                FunctionArgsMetadata {
+                    display_name: "Failure Handling",
+                    description: "What to do with the test when the check fails",
+                    arg_type: "failurehandling",
+                    default_value: "FailureHandling.Stop",
+                    optional: true,
+                },
+            ],
+            return_type: "void",
+            target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
         },
         BuiltinPkgFunctions::MUIInputTextboxByLabelExact => FunctionMetadata {
             name: "MUIInputTextboxByLabelExact",
@@ -475,16 +756,19 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "Input to send",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::MUI,
+            since_driver: DriverVersion::V0_2_0,
         },
         BuiltinPkgFunctions::MUIInputDropdownUsingTextByLabelExact => FunctionMetadata {
             name: "MUIInputDropdownUsingTextByLabelExact",
@@ -496,16 +780,19 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "Input to send",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::MUI,
+            since_driver: DriverVersion::V0_2_0,
         },
         BuiltinPkgFunctions::MUIInputDropdownUsingIndexByLabelExact => FunctionMetadata {
             name: "MUIInputDropdownUsingIndexByLabelExact",
@@ -517,16 +804,19 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "Input to send",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::MUI,
+            since_driver: DriverVersion::V0_2_0,
         },
         BuiltinPkgFunctions::MUIInputRadioUsingTextByLabelExact => FunctionMetadata {
             name: "MUIInputRadioUsingTextByLabelExact",
@@ -538,16 +828,19 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "Input to send",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::MUI,
+            since_driver: DriverVersion::V0_2_0,
         },
         BuiltinPkgFunctions::MUIInputRadioUsingIndexByLabelExact => FunctionMetadata {
             name: "MUIInputRadioUsingIndexByLabelExact",
@@ -559,16 +852,19 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "Input to send",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::MUI,
+            since_driver: DriverVersion::V0_2_0,
         },
         BuiltinPkgFunctions::MUIInputDateByLabelExact => FunctionMetadata {
             name: "MUIInputDateByLabelExact",
@@ -580,16 +876,19 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "Input to send",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::MUI,
+            since_driver: DriverVersion::V0_2_0,
         },
         BuiltinPkgFunctions::MUIInputTimeByLabelExact => FunctionMetadata {
             name: "MUIInputTimeByLabelExact",
@@ -601,16 +900,19 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "Input to send",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::MUI,
+            since_driver: DriverVersion::V0_2_0,
         },
         BuiltinPkgFunctions::MUIInputHtmlByLabelExact => FunctionMetadata {
             name: "MUIInputHtmlByLabelExact",
@@ -622,16 +924,176 @@ pub fn get_fn_metadata(builtin_fn: &BuiltinPkgFunctions) -> FunctionMetadata {
                     description: "Label to find",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
                 FunctionArgsMetadata {
                     display_name: "Input",
                     description: "Input to send",
                     arg_type: "string",
                     default_value: (""),
+                    optional: false,
                 },
             ],
             return_type: "void",
             target_ui: TargetUI::MUI,
+            since_driver: DriverVersion::V0_2_0,
+        },
+    }
+}
+
+pub fn get_element_method_metadata(method: &ElementMethods) -> FunctionMetadata {
+    match method {
+        ElementMethods::Click => FunctionMetadata {
+            name: "click",
+            display_name: "Click",
+            description: "Click the element",
+            args: vec![],
+            return_type: "void",
+            target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
+        },
+        ElementMethods::SendText => FunctionMetadata {
+            name: "sendText",
+            display_name: "Send Text",
+            description: "Send text into the element",
+            args: vec![FunctionArgsMetadata {
+                display_name: "Input",
+                description: "Input to send",
+                arg_type: "string",
+                default_value: (""),
+                optional: false,
+            }],
+            return_type: "void",
+            target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
+        },
+        ElementMethods::GetText => FunctionMetadata {
+            name: "getText",
+            display_name: "Get Text",
+            description: "Get the visible text of the element",
+            args: vec![],
+            return_type: "string",
+            target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
+        },
+    }
+}
+
+pub fn get_conversion_method_metadata(method: &ConversionMethods) -> FunctionMetadata {
+    match method {
+        ConversionMethods::ToString => FunctionMetadata {
+            name: "toString",
+            display_name: "To String",
+            description: "Convert the value into its text",
+            args: vec![],
+            return_type: "string",
+            target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
+        },
+        ConversionMethods::ToNumber => FunctionMetadata {
+            name: "toNumber",
+            display_name: "To Number",
+            description: "Convert the value into an integer, dropping the decimals",
+            args: vec![],
+            return_type: "number",
+            target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
+        },
+        ConversionMethods::ToBool => FunctionMetadata {
+            name: "toBool",
+            display_name: "To Bool",
+            description: "Convert the value into a bool, \"true\" in any case is true",
+            args: vec![],
+            return_type: "bool",
+            target_ui: TargetUI::Any,
+            since_driver: DriverVersion::V0_1_1,
+        },
+    }
+}
+
+/// Types of the values a conversion can be called on
+pub fn get_conversion_source_types(method: &ConversionMethods) -> &'static [&'static str] {
+    match method {
+        ConversionMethods::ToString => &["string", "number", "decimal", "bool"],
+        ConversionMethods::ToNumber => &["string", "number", "decimal"],
+        ConversionMethods::ToBool => &["string", "bool"],
+    }
+}
+
+pub fn get_enum_metadata(builtin_enum: &BuiltinPkgEnums) -> EnumMetadata {
+    match builtin_enum {
+        BuiltinPkgEnums::ByOption => EnumMetadata {
+            name: "ByOption",
+            description: "The attribute used to find an element",
+            type_name: "byoption",
+            members: vec![
+                EnumMemberMetadata {
+                    name: "Text",
+                    description: "Match by the element's text",
+                },
+                EnumMemberMetadata {
+                    name: "ID",
+                    description: "Match by the element's id attribute",
+                },
+                EnumMemberMetadata {
+                    name: "Class",
+                    description: "Match by the element's class attribute",
+                },
+                EnumMemberMetadata {
+                    name: "Name",
+                    description: "Match by the element's name attribute",
+                },
+                EnumMemberMetadata {
+                    name: "XPath",
+                    description: "Match by XPath expression",
+                },
+                EnumMemberMetadata {
+                    name: "CSS",
+                    description: "Match by CSS selector",
+                },
+            ],
+        },
+        BuiltinPkgEnums::FailureHandling => EnumMetadata {
+            name: "FailureHandling",
+            description: "What to do with the test when a check fails",
+            type_name: "failurehandling",
+            members: vec![
+                EnumMemberMetadata {
+                    name: "Stop",
+                    description: "Mark the test as failed and stop it",
+                },
+                EnumMemberMetadata {
+                    name: "Continue",
+                    description: "Mark the test as failed and continue running it",
+                },
+                EnumMemberMetadata {
+                    name: "Optional",
+                    description: "Log a warning and continue running the test",
+                },
+            ],
+        },
+    }
+}
+
+pub fn get_alias_metadata(alias: &BuiltinPkgAliases) -> AliasMetadata {
+    match alias {
+        BuiltinPkgAliases::InputTextboxByLabelExact => AliasMetadata {
+            name: "InputTextboxByLabelExact",
+            replacement: "ExtUIInputTextboxByLabelExact",
+            since_version: "0.2.3",
+            args: vec!["{arg1}", "{arg2}"],
+        },
+        BuiltinPkgAliases::ClickElementByText => AliasMetadata {
+            name: "ClickElementByText",
+            replacement: "ClickElementByString",
+            since_version: "0.2.3",
+            args: vec!["{arg1}", "ByOption.Text", "{arg2}", "true"],
+        },
+        BuiltinPkgAliases::GetElementByText => AliasMetadata {
+            name: "GetElementByText",
+            replacement: "GetElementByString",
+            since_version: "0.2.3",
+            args: vec!["{arg1}", "ByOption.Text", "{arg2}", "true"],
         },
     }
 }
@@ -1,37 +1,123 @@
 pub mod compat_check;
+pub mod config;
+pub mod const_fold;
+pub mod diagnostic;
 pub mod funcs;
+pub mod lint;
+pub mod scope;
+pub mod suggestion;
 pub mod tree_checker;
 pub mod var_checker;
 
-use std::collections::HashMap;
-
-use crate::autalonparser::Rule;
-use color_eyre::eyre::{bail, Report};
-use pest::iterators::Pair;
+use crate::{
+    ast,
+    autalonparser::{AutalonParser, Rule},
+    trivia,
+};
+use color_eyre::eyre::Report;
+use config::CheckerConfig;
+use diagnostic::Diagnostic;
+use pest::Parser;
 
 #[derive(Debug)]
-pub struct Checker<'a> {
-    var_table: HashMap<&'a str, &'a str>,
+pub struct Checker {
+    scopes: scope::ScopeStack,
+    config: CheckerConfig,
+    /// Problems found that don't stop the script from being transpiled
+    pub warnings: Vec<Diagnostic>,
 }
 
-pub fn statement_checker(pair: Pair<Rule>) -> Result<(), Report> {
-    let mut checker = Checker::new();
+/// Parse and check the whole program, collecting every problem found instead of stopping at the first one.
+pub fn check_program(code: &str) -> Vec<Diagnostic> {
+    let parsed = match AutalonParser::parse(Rule::program, code) {
+        Ok(parsed) => parsed,
+        Err(err) => return vec![Diagnostic::from_parse_error(&err)],
+    };
 
-    for statement in pair.into_inner() {
-        match statement.as_rule() {
-            Rule::expr => {
-                checker.get_expr_returntype(statement.into_inner())?;
-            }
-            Rule::var_declaration => {
-                checker.check_var_declaration(statement)?;
-            }
-            Rule::var_assignment => {
-                checker.check_var_assignment(statement)?;
+    let mut diagnostics = vec![];
+
+    let mut cfg = vec![];
+    for pair in parsed
+        .clone()
+        .filter(|pair| pair.as_rule() == Rule::cfg_section)
+    {
+        let span = pair.as_span();
+
+        match ast::build_cfg(pair) {
+            Ok(entry) => cfg.push(entry),
+            Err(err) => diagnostics.push(Diagnostic::error(format!("{err:#}"), span)),
+        }
+    }
+
+    let statements = trivia::annotate(code, parsed)
+        .statements
+        .into_iter()
+        .map(|x| {
+            let span = x.pair.as_span().into();
+            ast::build_statement(x.pair, x.trivia).map_err(|err| (err, span))
+        })
+        .collect();
+
+    diagnostics.extend(check_statements(&cfg, statements).1);
+
+    diagnostics
+}
+
+/// Check a parsed program, collecting every problem found. Types are annotated on the
+/// expressions of the statements that pass.
+pub fn check_ast(program: &mut ast::Program) -> Vec<Diagnostic> {
+    let statements = std::mem::take(&mut program.statements)
+        .into_iter()
+        .map(Ok)
+        .collect();
+
+    let (statements, diagnostics) = check_statements(&program.cfg, statements);
+    program.statements = statements;
+
+    diagnostics
+}
+
+/// Check a parsed program, stopping at the first error. Returns the warnings found.
+pub fn check_parsed_program(program: &mut ast::Program) -> Result<Vec<Diagnostic>, Report> {
+    let mut diagnostics = check_ast(program);
+
+    match diagnostics.iter().position(|x| x.is_error()) {
+        Some(i) => Err(diagnostics.swap_remove(i).into()),
+        None => Ok(diagnostics),
+    }
+}
+
+/// Check the statements of a program in order, with the options of its cfg sections.
+/// `statements` holds the error and span of the ones that couldn't be built. Returns the
+/// statements that were built, and the problems found in the cfg sections and the statements.
+fn check_statements(
+    cfg: &[ast::CfgEntry],
+    statements: Vec<Result<ast::Statement, (Report, ast::Span)>>,
+) -> (Vec<ast::Statement>, Vec<Diagnostic>) {
+    let constants =
+        const_fold::program_constants(statements.iter().filter_map(|x| x.as_ref().ok()));
+
+    // An unknown option keeps its default, so that the statements are still checked
+    let (config, mut diagnostics) = CheckerConfig::from_cfg(cfg, &constants);
+
+    // Declarations are visible to the statements after them
+    let mut checker = Checker::with_config(config.clone());
+    let mut checked = vec![];
+    for statement in statements {
+        match statement {
+            Ok(mut statement) => {
+                if let Err(err) = checker.check_statement(&mut statement) {
+                    diagnostics.push(Diagnostic::from_report(err, statement.span));
+                }
+                checked.push(statement);
             }
-            Rule::escape_block => (),
-            nonmatch => bail!("{nonmatch:?} doesn't match any return type!"),
-        };
+            Err((err, span)) => diagnostics.push(Diagnostic::from_report(err, span)),
+        }
+
+        diagnostics.append(&mut checker.warnings);
     }
 
-    Ok(())
+    diagnostics.extend(lint::lint_program(cfg, &checked, &config));
+
+    (checked, diagnostics)
 }
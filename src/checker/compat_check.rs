@@ -1,6 +1,6 @@
 use color_eyre::eyre::{eyre, Report};
 
-use crate::autalonparser::Rule;
+use crate::ast::{BinaryOp, UnaryOp};
 
 #[tracing::instrument]
 pub fn check_type_arithmetic(lhs: &str, rhs: &str) -> Result<(), Report> {
@@ -8,6 +8,7 @@ pub fn check_type_arithmetic(lhs: &str, rhs: &str) -> Result<(), Report> {
     let map = std::collections::HashMap::from([
         ("string", vec!["string"]),
         ("number", vec!["number"]),
+        ("decimal", vec!["decimal"]),
         ("bool", vec!["bool"]),
     ]);
 
@@ -23,19 +24,30 @@ pub fn check_type_arithmetic(lhs: &str, rhs: &str) -> Result<(), Report> {
 }
 
 #[tracing::instrument]
-pub fn check_arithmetic_op(rhs: &str, optype: Rule) -> Result<(), Report> {
+pub fn check_arithmetic_op(rhs: &str, optype: BinaryOp) -> Result<(), Report> {
     // TODO: Use oncecell for these kind of types maybe?
     let map = std::collections::HashMap::from([
-        ("string", vec![Rule::add_op]),
+        ("string", vec![BinaryOp::Add]),
         (
             "number",
             vec![
-                Rule::add_op,
-                Rule::sub_op,
-                Rule::div_op,
-                Rule::mul_op,
-                Rule::mod_op,
-                Rule::pow_op,
+                BinaryOp::Add,
+                BinaryOp::Sub,
+                BinaryOp::Div,
+                BinaryOp::Mul,
+                BinaryOp::Mod,
+                BinaryOp::Pow,
+            ],
+        ),
+        (
+            "decimal",
+            vec![
+                BinaryOp::Add,
+                BinaryOp::Sub,
+                BinaryOp::Div,
+                BinaryOp::Mul,
+                BinaryOp::Mod,
+                BinaryOp::Pow,
             ],
         ),
     ]);
@@ -45,27 +57,38 @@ pub fn check_arithmetic_op(rhs: &str, optype: Rule) -> Result<(), Report> {
             "Cannot find type '{rhs}' for arithmetic operation compatibility checking."
         )),
         Some(val) => match val.iter().position(|x| x == &optype) {
-            None => Err(eyre!("Type '{rhs}' is incompatible with '{optype:?}'!")),
+            None => Err(eyre!("Type '{rhs}' is incompatible with '{optype}'!")),
             Some(_) => Ok(()),
         },
     }
 }
 
 #[tracing::instrument]
-pub fn check_comparation_op(rhs: &str, op_type: Rule) -> Result<(), Report> {
+pub fn check_comparation_op(rhs: &str, op_type: BinaryOp) -> Result<(), Report> {
     // TODO: Use oncecell for these kind of types maybe?
     let map = std::collections::HashMap::from([
-        ("string", vec![Rule::eq_op, Rule::ne_op]),
-        ("bool", vec![Rule::eq_op, Rule::ne_op]),
+        ("string", vec![BinaryOp::Eq, BinaryOp::Ne]),
+        ("bool", vec![BinaryOp::Eq, BinaryOp::Ne]),
         (
             "number",
             vec![
-                Rule::eq_op,
-                Rule::ne_op,
-                Rule::lt_op,
-                Rule::le_op,
-                Rule::gt_op,
-                Rule::ge_op,
+                BinaryOp::Eq,
+                BinaryOp::Ne,
+                BinaryOp::Lt,
+                BinaryOp::Le,
+                BinaryOp::Gt,
+                BinaryOp::Ge,
+            ],
+        ),
+        (
+            "decimal",
+            vec![
+                BinaryOp::Eq,
+                BinaryOp::Ne,
+                BinaryOp::Lt,
+                BinaryOp::Le,
+                BinaryOp::Gt,
+                BinaryOp::Ge,
             ],
         ),
     ]);
@@ -75,8 +98,46 @@ pub fn check_comparation_op(rhs: &str, op_type: Rule) -> Result<(), Report> {
             "Cannot find type for type comparation compatibility checking."
         )),
         Some(val) => match val.iter().position(|x| x == &op_type) {
-            None => Err(eyre!("Type '{rhs}' is incompatible with '{op_type:?}'!")),
+            None => Err(eyre!("Type '{rhs}' is incompatible with '{op_type}'!")),
             Some(_) => Ok(()),
         },
     }
 }
+
+#[tracing::instrument]
+pub fn check_unary_op(operand: &str, op_type: UnaryOp) -> Result<(), Report> {
+    let expected: &[&str] = match op_type {
+        UnaryOp::Neg => &["number", "decimal"],
+        UnaryOp::Not => &["bool"],
+    };
+
+    if !expected.contains(&operand) {
+        return Err(eyre!("Type '{operand}' is incompatible with '{op_type}'!"));
+    }
+
+    Ok(())
+}
+
+/// Types of the operands of a binary operation, with integers widened when mixed with decimals
+pub fn promote_numeric(lhs: &'static str, rhs: &'static str) -> (&'static str, &'static str) {
+    match (lhs, rhs) {
+        ("number", "decimal") | ("decimal", "number") => ("decimal", "decimal"),
+        types => types,
+    }
+}
+
+/// Whether a value of type `from` can be used where `to` is expected. Integers widen into
+/// decimals, decimals never narrow into integers.
+pub fn is_assignable(from: &str, to: &str) -> bool {
+    from == to || (from == "number" && to == "decimal")
+}
+
+/// Only plain values read well when put into a string
+#[tracing::instrument]
+pub fn check_interpolation(value: &str) -> Result<(), Report> {
+    if !["string", "number", "decimal", "bool"].contains(&value) {
+        return Err(eyre!("Type '{value}' can't be put into a string!"));
+    }
+
+    Ok(())
+}
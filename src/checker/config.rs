@@ -0,0 +1,112 @@
+use std::str::FromStr;
+
+use color_eyre::eyre::{eyre, Report};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumString};
+
+use crate::{
+    ast::{CfgEntry, Program},
+    builtin_package_definition::{DriverVersion, TargetUI},
+};
+
+use super::{
+    const_fold::{self, Constants},
+    diagnostic::Diagnostic,
+    lint::LintRule,
+};
+
+/// What happens to values of other types added to a string, set with `#[coercion=...]`
+#[derive(Debug, Clone, Copy, PartialEq, Default, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Coercion {
+    /// Values have to be converted explicitly, e.g. with `toString()`
+    #[default]
+    Strict,
+    /// Numbers and bools are converted into their text
+    Concat,
+}
+
+/// Checking options a script sets in its cfg sections
+#[derive(Debug, Clone, Default)]
+pub struct CheckerConfig {
+    pub coercion: Coercion,
+    /// UI framework declared with `#[ui=...]`, which limits the builtins that can be called
+    pub ui: Option<TargetUI>,
+    /// Lint rules turned off with `#[allow=...]`
+    pub allowed: Vec<LintRule>,
+    /// Driver version the script is pinned to with `#[driver_ver=...]`, which limits the builtins
+    /// that can be called
+    pub driver: DriverVersion,
+}
+
+impl CheckerConfig {
+    /// Read the options from the cfg sections of a program, which may name its constants
+    pub fn from_program(program: &Program) -> Result<CheckerConfig, Report> {
+        let constants = const_fold::program_constants(&program.statements);
+
+        match CheckerConfig::from_cfg(&program.cfg, &constants) {
+            (config, errors) if errors.is_empty() => Ok(config),
+            (_, mut errors) => Err(errors.remove(0).into()),
+        }
+    }
+
+    /// Read the options from cfg sections, which may name `constants`. Options with an unknown
+    /// value keep their default, and their problem is returned.
+    pub fn from_cfg(cfg: &[CfgEntry], constants: &Constants) -> (CheckerConfig, Vec<Diagnostic>) {
+        let mut config = CheckerConfig::default();
+        let mut errors = vec![];
+
+        for entry in cfg {
+            if let Err(err) = config.apply(&const_fold::resolve_cfg(entry, constants)) {
+                errors.push(Diagnostic::error(format!("{err:#}"), entry.span));
+            }
+        }
+
+        (config, errors)
+    }
+
+    /// Set the option of a cfg section, ignoring the ones unrelated to checking
+    pub fn apply(&mut self, entry: &CfgEntry) -> Result<(), Report> {
+        if entry.key == "coercion" {
+            self.coercion = Coercion::from_str(&entry.value).map_err(|_| {
+                eyre!(
+                    "Unknown coercion '{}', expected 'strict' or 'concat'",
+                    entry.value
+                )
+            })?;
+        }
+
+        if entry.key == "ui" {
+            self.ui = Some(TargetUI::from_str(&entry.value).map_err(|_| {
+                eyre!(
+                    "Unknown UI framework '{}', expected 'ext', 'mui' or 'any'",
+                    entry.value
+                )
+            })?);
+        }
+
+        if entry.key == "driver_ver" {
+            self.driver = DriverVersion::from_str(&entry.value).map_err(|_| {
+                eyre!(
+                    "Unknown driver version '{}', expected one of {}",
+                    entry.value,
+                    DriverVersion::iter()
+                        .map(|x| x.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+            })?;
+        }
+
+        if entry.key == "allow" {
+            match entry.value.as_str() {
+                "*" => self.allowed.extend(LintRule::iter()),
+                value => self.allowed.push(
+                    LintRule::from_str(value).map_err(|_| eyre!("Unknown lint rule '{value}'"))?,
+                ),
+            }
+        }
+
+        Ok(())
+    }
+}
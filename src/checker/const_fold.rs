@@ -0,0 +1,162 @@
+//! Evaluation of constant expressions, to warn about operations that can't give the expected
+//! result at runtime, and to know the values of `const` declarations.
+
+use std::collections::HashMap;
+
+use crate::ast::{
+    self, BinaryOp, CfgEntry, Expr, ExprKind, Literal, Statement, StatementKind, StringPart,
+    UnaryOp,
+};
+
+/// Values of the constants of a program, by name
+pub type Constants = HashMap<String, Literal>;
+
+/// Numbers are 32-bit integers in the generated code, which wrap around outside of this range
+fn in_range(value: i64) -> bool {
+    i32::try_from(value).is_ok()
+}
+
+fn apply(op: BinaryOp, lhs: i64, rhs: i64) -> Option<i64> {
+    match op {
+        BinaryOp::Add => lhs.checked_add(rhs),
+        BinaryOp::Sub => lhs.checked_sub(rhs),
+        BinaryOp::Mul => lhs.checked_mul(rhs),
+        // Quotients that aren't whole are decimals
+        BinaryOp::Div => lhs.checked_rem(rhs).filter(|x| *x == 0).map(|_| lhs / rhs),
+        BinaryOp::Mod => lhs.checked_rem(rhs),
+        BinaryOp::Pow => u32::try_from(rhs).ok().and_then(|x| lhs.checked_pow(x)),
+        _ => None,
+    }
+}
+
+/// Value of an expression made of literals and constants, looked up with `constant`. Strings are
+/// kept as written, with their escape sequences.
+pub fn evaluate(expr: &Expr, constant: &dyn Fn(&str) -> Option<Literal>) -> Option<Literal> {
+    match &expr.kind {
+        ExprKind::Literal(literal) => Some(literal.clone()),
+        ExprKind::Variable(identifier) => constant(&identifier.name),
+        ExprKind::Interpolation(parts) => parts
+            .iter()
+            .map(|part| match part {
+                StringPart::Text(text) => Some(text.to_owned()),
+                StringPart::Expr(expr) => match evaluate(expr, constant)? {
+                    Literal::String(value) => Some(value),
+                    literal @ (Literal::Number(_) | Literal::Bool(_)) => {
+                        Some(literal_text(&literal))
+                    }
+                    // Groovy prints decimals its own way
+                    Literal::Decimal(_) => None,
+                },
+            })
+            .collect::<Option<String>>()
+            .map(Literal::String),
+        ExprKind::Unary { op, operand } => match (op, evaluate(operand, constant)?) {
+            (UnaryOp::Neg, Literal::Number(value)) => {
+                number(value.parse::<i64>().ok()?.checked_neg())
+            }
+            (UnaryOp::Not, Literal::Bool(value)) => Some(Literal::Bool(!value)),
+            _ => None,
+        },
+        ExprKind::Binary { op, lhs, rhs } => {
+            match (op, evaluate(lhs, constant)?, evaluate(rhs, constant)?) {
+                (BinaryOp::Add, Literal::String(lhs), Literal::String(rhs)) => {
+                    Some(Literal::String(lhs + &rhs))
+                }
+                (op, Literal::Number(lhs), Literal::Number(rhs)) => {
+                    number(apply(*op, lhs.parse().ok()?, rhs.parse().ok()?))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn number(value: Option<i64>) -> Option<Literal> {
+    value
+        .filter(|x| in_range(*x))
+        .map(|x| Literal::Number(x.to_string()))
+}
+
+/// Text of a value, as it reads in a string or a cfg section
+fn literal_text(literal: &Literal) -> String {
+    match literal {
+        Literal::String(value) => ast::unescape(value),
+        Literal::Number(value) | Literal::Decimal(value) => value.to_owned(),
+        Literal::Bool(value) => value.to_string(),
+    }
+}
+
+/// Values of the `const` declarations of a program, skipping the ones that can't be evaluated
+pub fn program_constants<'a>(statements: impl IntoIterator<Item = &'a Statement>) -> Constants {
+    let mut constants = Constants::new();
+
+    for statement in statements {
+        if let StatementKind::VarDeclaration {
+            name,
+            value,
+            constant: true,
+        } = &statement.kind
+        {
+            if let Some(literal) = evaluate(value, &|x| constants.get(x).cloned()) {
+                constants.entry(name.name.to_owned()).or_insert(literal);
+            }
+        }
+    }
+
+    constants
+}
+
+/// cfg entry with a value naming a constant replaced by the value of the constant
+pub fn resolve_cfg(entry: &CfgEntry, constants: &Constants) -> CfgEntry {
+    match constants.get(&entry.value) {
+        Some(literal) => CfgEntry {
+            value: literal_text(literal),
+            ..entry.clone()
+        },
+        None => entry.clone(),
+    }
+}
+
+/// Warning for an arithmetic operation on constants that goes wrong at runtime. Constants are
+/// looked up with `constant`.
+pub fn binary_warning(
+    op: BinaryOp,
+    lhs: &Expr,
+    rhs: &Expr,
+    constant: &dyn Fn(&str) -> Option<Literal>,
+) -> Option<String> {
+    let rhs_value = evaluate(rhs, constant);
+
+    match op {
+        BinaryOp::Div | BinaryOp::Mod if rhs_value.as_ref().is_some_and(is_zero) => {
+            Some("Division by zero".to_string())
+        }
+        // Powers are widened by Groovy instead of overflowing
+        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul => {
+            let (Some(Literal::Number(lhs_value)), Some(Literal::Number(rhs_value))) =
+                (evaluate(lhs, constant), rhs_value)
+            else {
+                return None;
+            };
+            let (lhs_value, rhs_value) = (lhs_value.parse().ok()?, rhs_value.parse().ok()?);
+
+            match apply(op, lhs_value, rhs_value) {
+                Some(value) if in_range(value) => None,
+                _ => Some(format!(
+                    "'{lhs_value} {op} {rhs_value}' overflows a 32-bit integer"
+                )),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Whether a number or a decimal is zero, which BigDecimal can't divide by either
+fn is_zero(literal: &Literal) -> bool {
+    match literal {
+        Literal::Number(value) => value.parse::<i64>() == Ok(0),
+        Literal::Decimal(value) => value.parse::<f64>() == Ok(0.0),
+        _ => false,
+    }
+}
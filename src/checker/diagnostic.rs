@@ -0,0 +1,196 @@
+use color_eyre::eyre::Report;
+use pest::error::Error;
+use serde::Serialize;
+use strum_macros::Display;
+
+use crate::{ast::Span, autalonparser::Rule};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Display)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// Byte offset where the diagnostic starts
+    pub start: usize,
+    /// Byte offset where the diagnostic ends
+    pub end: usize,
+    /// 1-based line of the diagnostic start
+    pub line: usize,
+    /// 1-based column of the diagnostic start
+    pub column: usize,
+    /// Name of the lint rule that reported the diagnostic
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<&'static str>,
+    /// Other places of the script the diagnostic refers to
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<RelatedLocation>,
+    /// Edits that solve the problem
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fixes: Vec<Fix>,
+}
+
+/// Replacement of a range of the script, offered to solve a diagnostic
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fix {
+    pub title: String,
+    pub start: usize,
+    pub end: usize,
+    pub replacement: String,
+    /// Whether the fix surely solves the problem. Guesses, like the correction of a typo, aren't
+    /// applied by the `fix` command.
+    pub preferred: bool,
+}
+
+impl Fix {
+    pub fn new(
+        title: impl Into<String>,
+        span: impl Into<Span>,
+        replacement: impl Into<String>,
+        preferred: bool,
+    ) -> Fix {
+        let span = span.into();
+
+        Fix {
+            title: title.into(),
+            start: span.start,
+            end: span.end,
+            replacement: replacement.into(),
+            preferred,
+        }
+    }
+}
+
+/// A place of the script related to a diagnostic, like the original declaration of a variable
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelatedLocation {
+    pub message: String,
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Checking errors that know their own location are returned as diagnostics
+impl std::error::Error for Diagnostic {}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>, span: impl Into<Span>) -> Diagnostic {
+        Diagnostic::new(Severity::Error, message, span)
+    }
+
+    pub fn warning(message: impl Into<String>, span: impl Into<Span>) -> Diagnostic {
+        Diagnostic::new(Severity::Warning, message, span)
+    }
+
+    pub fn new(
+        severity: Severity,
+        message: impl Into<String>,
+        span: impl Into<Span>,
+    ) -> Diagnostic {
+        let span = span.into();
+
+        Diagnostic {
+            severity,
+            message: message.into(),
+            start: span.start,
+            end: span.end,
+            line: span.line,
+            column: span.column,
+            code: None,
+            related: vec![],
+            fixes: vec![],
+        }
+    }
+
+    pub fn with_fix(
+        mut self,
+        title: impl Into<String>,
+        span: impl Into<Span>,
+        replacement: impl Into<String>,
+    ) -> Diagnostic {
+        self.fixes.push(Fix::new(title, span, replacement, true));
+        self
+    }
+
+    /// Add a fix that guesses what was meant
+    pub fn with_suggestion(
+        mut self,
+        title: impl Into<String>,
+        span: impl Into<Span>,
+        replacement: impl Into<String>,
+    ) -> Diagnostic {
+        self.fixes.push(Fix::new(title, span, replacement, false));
+        self
+    }
+
+    pub fn with_code(mut self, code: &'static str) -> Diagnostic {
+        self.code = Some(code);
+        self
+    }
+
+    pub fn with_related(mut self, message: impl Into<String>, span: impl Into<Span>) -> Diagnostic {
+        let span = span.into();
+
+        self.related.push(RelatedLocation {
+            message: message.into(),
+            start: span.start,
+            end: span.end,
+            line: span.line,
+            column: span.column,
+        });
+        self
+    }
+
+    /// Diagnostic of a checking error. Errors without a location of their own are put at `span`.
+    pub fn from_report(err: Report, span: impl Into<Span>) -> Diagnostic {
+        match err.downcast::<Diagnostic>() {
+            Ok(diagnostic) => diagnostic,
+            Err(err) => Diagnostic::error(format!("{err:#}"), span),
+        }
+    }
+
+    pub fn from_parse_error(err: &Error<Rule>) -> Diagnostic {
+        use pest::error::{InputLocation, LineColLocation};
+
+        let (start, end) = match err.location {
+            InputLocation::Pos(pos) => (pos, pos),
+            InputLocation::Span(span) => span,
+        };
+        let (line, column) = match err.line_col {
+            LineColLocation::Pos(pos) => pos,
+            LineColLocation::Span(start, _) => start,
+        };
+
+        Diagnostic {
+            severity: Severity::Error,
+            message: format!("Failed to parse: {}", err.variant.message()),
+            start,
+            end,
+            line,
+            column,
+            code: None,
+            related: vec![],
+            fixes: vec![],
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
@@ -0,0 +1,397 @@
+//! Warnings about scripts that check fine, but likely don't do what was meant.
+//!
+//! Every rule is on by default. A rule is turned off for the whole script with
+//! `#[allow=rule_name]` (`#[allow=*]` turns off all of them), or for one statement with an
+//! `// atln-allow(rule_name, ...)` comment before or after it.
+//!
+//! `mixed_ui` only applies to scripts without `#[ui=...]`, the checker rejects calls that don't
+//! match the declared UI framework.
+
+use std::{collections::HashMap, str::FromStr};
+
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString, IntoStaticStr};
+
+use crate::{
+    ast::{CfgEntry, Expr, ExprKind, Literal, Span, Statement, StatementKind},
+    builtin_package_definition::{get_fn_metadata, BuiltinPkgFunctions, TargetUI},
+};
+
+use super::{config::CheckerConfig, const_fold, diagnostic::Diagnostic, tree_checker};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, EnumIter, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum LintRule {
+    /// Variable that is never read nor assigned
+    UnusedVariable,
+    /// Variable that is assigned but never read
+    UnreadVariable,
+    /// Statement after an assertion that always stops the test
+    UnreachableCode,
+    /// Navigation right after another one, which makes the first one useless
+    DuplicateNavigation,
+    /// ExtUI and MUI functions in the same script
+    MixedUi,
+    /// Literal text typed into a password-like field
+    HardcodedCredential,
+}
+
+/// Words in an element locator or a field label that tell it's a credential field
+const CREDENTIAL_WORDS: [&str; 5] = ["pass", "pwd", "secret", "token", "apikey"];
+
+/// Lint warnings of a checked program, without the ones the script allows
+pub fn lint_program(
+    cfg: &[CfgEntry],
+    statements: &[Statement],
+    config: &CheckerConfig,
+) -> Vec<Diagnostic> {
+    let constants = const_fold::program_constants(statements);
+
+    let mut warnings = vec![];
+    warnings.extend(unused_variables(cfg, statements));
+    warnings.extend(unreachable_code(statements, &constants));
+    warnings.extend(duplicate_navigations(statements));
+    // Calls of another framework than the declared one are errors already
+    if config.ui.is_none() {
+        warnings.extend(mixed_ui(statements));
+    }
+    warnings.extend(hardcoded_credentials(statements, &constants));
+
+    warnings
+        .into_iter()
+        .filter(|(rule, warning)| {
+            !config.allowed.contains(rule) && !inline_allows(statements, warning, *rule)
+        })
+        .map(|(rule, warning)| warning.with_code(rule.into()))
+        .collect()
+}
+
+/// Rules named by the `atln-allow(...)` comments
+pub fn parse_allow_comment(content: &str) -> Vec<LintRule> {
+    let Some((_, rest)) = content.split_once("atln-allow(") else {
+        return vec![];
+    };
+    let rules = rest.split_once(')').map_or(rest, |(x, _)| x);
+
+    rules
+        .split(',')
+        .filter_map(|x| match x.trim() {
+            "*" => Some(LintRule::iter().collect()),
+            x => LintRule::from_str(x).ok().map(|x| vec![x]),
+        })
+        .flatten()
+        .collect()
+}
+
+/// Whether the comments of the statement the warning is in allow its rule
+fn inline_allows(statements: &[Statement], warning: &Diagnostic, rule: LintRule) -> bool {
+    statements
+        .iter()
+        .filter(|x| x.span.start <= warning.start && warning.end <= x.span.end)
+        .flat_map(|x| x.trivia.leading.iter().chain(&x.trivia.trailing))
+        .any(|x| parse_allow_comment(&x.content).contains(&rule))
+}
+
+/// Name of the builtin a statement calls directly, with its arguments
+fn builtin_call(statement: &Statement) -> Option<(&'static str, &[Expr])> {
+    match &statement.kind {
+        StatementKind::Expr(Expr {
+            kind:
+                ExprKind::Call {
+                    builtin: Some(builtin),
+                    args,
+                    ..
+                },
+            ..
+        }) => Some((builtin, args)),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct VariableUsage {
+    declaration: Option<Span>,
+    read: bool,
+    assigned: bool,
+}
+
+fn unused_variables(cfg: &[CfgEntry], statements: &[Statement]) -> Vec<(LintRule, Diagnostic)> {
+    // Kept in declaration order, so the warnings are too
+    let mut names: Vec<String> = vec![];
+    let mut usages: HashMap<String, VariableUsage> = HashMap::new();
+
+    for statement in statements {
+        if let StatementKind::VarDeclaration { name, .. } = &statement.kind {
+            names.push(name.name.to_owned());
+            usages
+                .entry(name.name.to_owned())
+                .or_default()
+                .declaration
+                .get_or_insert(name.span);
+        }
+    }
+
+    for statement in statements {
+        if let Some(expr) = statement.kind.expr() {
+            expr.walk(&mut |x| {
+                if let ExprKind::Variable(identifier) = &x.kind {
+                    usages.entry(identifier.name.to_owned()).or_default().read = true;
+                }
+            });
+        }
+
+        if let StatementKind::VarAssignment { name, .. } = &statement.kind {
+            usages.entry(name.name.to_owned()).or_default().assigned = true;
+        }
+    }
+
+    // Constants can be named by cfg values
+    for entry in cfg {
+        usages.entry(entry.value.to_owned()).or_default().read = true;
+    }
+
+    names
+        .into_iter()
+        .filter_map(|name| usages.remove(&name).map(|usage| (name, usage)))
+        .filter_map(|(name, usage)| match usage {
+            VariableUsage {
+                declaration: Some(declaration),
+                read: false,
+                assigned: false,
+            } => Some((
+                LintRule::UnusedVariable,
+                Diagnostic::warning(format!("Variable \"{name}\" is never used"), declaration),
+            )),
+            VariableUsage {
+                declaration: Some(declaration),
+                read: false,
+                assigned: true,
+            } => Some((
+                LintRule::UnreadVariable,
+                Diagnostic::warning(
+                    format!("Variable \"{name}\" is assigned but never read"),
+                    declaration,
+                ),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `#:Assert(false)` fails the test and stops it, unless given a failure handling that goes on
+fn is_terminating(statement: &Statement, constants: &const_fold::Constants) -> bool {
+    let Some(("Assert", [condition, failure_handling @ ..])) = builtin_call(statement) else {
+        return false;
+    };
+
+    let always_fails = const_fold::evaluate(condition, &|x| constants.get(x).cloned())
+        == Some(Literal::Bool(false));
+    let is_stop = |x: &Expr| {
+        matches!(
+            &x.kind,
+            ExprKind::EnumMember { member, .. } if member.name == "Stop"
+        )
+    };
+    // Left out, the failure handling is its default
+    let stops = match failure_handling.first() {
+        Some(failure_handling) => is_stop(failure_handling),
+        None => get_fn_metadata(&BuiltinPkgFunctions::Assert)
+            .args
+            .get(1)
+            .and_then(|x| tree_checker::default_arg(x, statement.span).ok())
+            .is_some_and(|x| is_stop(&x)),
+    };
+
+    always_fails && stops
+}
+
+fn unreachable_code(
+    statements: &[Statement],
+    constants: &const_fold::Constants,
+) -> Vec<(LintRule, Diagnostic)> {
+    let Some(terminating) = statements.iter().position(|x| is_terminating(x, constants)) else {
+        return vec![];
+    };
+
+    statements[terminating + 1..]
+        .iter()
+        .find(|x| !matches!(x.kind, StatementKind::Escape(_)))
+        .map(|x| {
+            (
+                LintRule::UnreachableCode,
+                Diagnostic::warning(
+                    format!(
+                        "Unreachable statement, the test stops at the assertion on line {}",
+                        statements[terminating].span.line
+                    ),
+                    x.span,
+                ),
+            )
+        })
+        .into_iter()
+        .collect()
+}
+
+fn duplicate_navigations(statements: &[Statement]) -> Vec<(LintRule, Diagnostic)> {
+    statements
+        .windows(2)
+        .filter(|pair| {
+            pair.iter()
+                .all(|x| matches!(builtin_call(x), Some(("NavigateToUrl", _))))
+        })
+        .map(|pair| {
+            (
+                LintRule::DuplicateNavigation,
+                Diagnostic::warning(
+                    format!(
+                        "Navigating again right away, the navigation on line {} has no effect",
+                        pair[0].span.line
+                    ),
+                    pair[1].span,
+                ),
+            )
+        })
+        .collect()
+}
+
+fn mixed_ui(statements: &[Statement]) -> Vec<(LintRule, Diagnostic)> {
+    let mut calls = vec![];
+    for statement in statements {
+        if let Some(expr) = statement.kind.expr() {
+            expr.walk(&mut |x| {
+                if let ExprKind::Call {
+                    builtin: Some(builtin),
+                    ..
+                } = &x.kind
+                {
+                    calls.push((*builtin, x.span));
+                }
+            });
+        }
+    }
+
+    let target_ui = |name: &str| {
+        BuiltinPkgFunctions::from_str(name)
+            .map(|x| get_fn_metadata(&x).target_ui)
+            .unwrap_or(TargetUI::Any)
+    };
+
+    let Some((first_name, first_span)) = calls
+        .iter()
+        .find(|(name, _)| target_ui(name) != TargetUI::Any)
+    else {
+        return vec![];
+    };
+    let first_ui = target_ui(first_name);
+
+    calls
+        .iter()
+        .filter(|(name, _)| !matches!(target_ui(name), TargetUI::Any) && target_ui(name) != first_ui)
+        .map(|(name, span)| {
+            (
+                LintRule::MixedUi,
+                Diagnostic::warning(
+                    format!(
+                        "\"{name}\" is a {} function, but \"{first_name}\" on line {} is a {first_ui} function",
+                        target_ui(name),
+                        first_span.line
+                    ),
+                    *span,
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Builtins that type their second argument into the field their first argument locates or labels
+const TEXT_INPUTS: [&str; 3] = [
+    "SendTextToElementByString",
+    "ExtUIInputTextboxByLabelExact",
+    "MUIInputTextboxByLabelExact",
+];
+
+/// Locator or label of the field an element comes from, when it's written as a literal
+fn field_name<'a>(element: &'a Expr, fields: &HashMap<&str, &'a str>) -> Option<&'a str> {
+    match &element.kind {
+        ExprKind::Call {
+            builtin: Some(_),
+            args,
+            ..
+        } => match &args.first()?.kind {
+            ExprKind::Literal(Literal::String(field)) => Some(field),
+            _ => None,
+        },
+        ExprKind::Variable(identifier) => fields.get(identifier.name.as_str()).copied(),
+        _ => None,
+    }
+}
+
+fn hardcoded_credentials(
+    statements: &[Statement],
+    constants: &const_fold::Constants,
+) -> Vec<(LintRule, Diagnostic)> {
+    // Fields the element variables come from, as far as the statements got
+    let mut fields: HashMap<&str, &str> = HashMap::new();
+    let mut typed = vec![];
+
+    for statement in statements {
+        match &statement.kind {
+            StatementKind::VarDeclaration { name, value, .. }
+            | StatementKind::VarAssignment { name, value } => {
+                match field_name(value, &fields) {
+                    Some(field) => fields.insert(&name.name, field),
+                    None => fields.remove(name.name.as_str()),
+                };
+            }
+            StatementKind::Expr(Expr {
+                kind:
+                    ExprKind::MethodCall {
+                        receiver,
+                        name,
+                        args,
+                    },
+                ..
+            }) if name.name == "sendText" => {
+                if let (Some(field), Some(input)) = (field_name(receiver, &fields), args.first()) {
+                    typed.push((field, input));
+                }
+            }
+            StatementKind::Expr(
+                call @ Expr {
+                    kind:
+                        ExprKind::Call {
+                            builtin: Some(name),
+                            args,
+                            ..
+                        },
+                    ..
+                },
+            ) if TEXT_INPUTS.contains(name) => {
+                if let (Some(field), Some(input)) = (field_name(call, &fields), args.get(1)) {
+                    typed.push((field, input));
+                }
+            }
+            _ => (),
+        }
+    }
+
+    typed
+        .into_iter()
+        .filter(|(field, _)| {
+            let field_lowercase = field.to_lowercase();
+            CREDENTIAL_WORDS.iter().any(|x| field_lowercase.contains(x))
+        })
+        .filter_map(|(field, input)| {
+            match const_fold::evaluate(input, &|x| constants.get(x).cloned())? {
+                Literal::String(value) if !value.is_empty() => Some((
+                    LintRule::HardcodedCredential,
+                    Diagnostic::warning(
+                        format!("Hard-coded credential typed into \"{field}\""),
+                        input.span,
+                    ),
+                )),
+                _ => None,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,121 @@
+//! Variables visible at a point of the script. The whole program is the outermost scope, blocks
+//! open nested ones.
+//!
+//! Shadowing rules:
+//!
+//! - Declaring a name twice in the same scope is an error.
+//! - Declaring a name of an outer scope shadows it until the inner scope ends, with a warning.
+//! - Constants can't be shadowed.
+
+use std::collections::HashMap;
+
+use color_eyre::eyre::Report;
+
+use crate::ast::{Identifier, Literal, Span};
+
+use super::diagnostic::Diagnostic;
+
+#[derive(Debug, Clone)]
+pub struct Variable {
+    pub var_type: &'static str,
+    /// Span of the variable name in its declaration
+    pub declaration: Span,
+    /// Value of constants, `None` for variables
+    pub value: Option<Literal>,
+}
+
+#[derive(Debug)]
+pub struct ScopeStack {
+    scopes: Vec<HashMap<String, Variable>>,
+}
+
+impl Default for ScopeStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScopeStack {
+    pub fn new() -> ScopeStack {
+        ScopeStack {
+            scopes: vec![HashMap::new()],
+        }
+    }
+
+    pub fn push(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    /// End the innermost scope. The program scope is never ended.
+    pub fn pop(&mut self) {
+        if self.scopes.len() > 1 {
+            self.scopes.pop();
+        }
+    }
+
+    /// Innermost variable with the given name
+    pub fn lookup(&self, name: &str) -> Option<&Variable> {
+        self.scopes.iter().rev().find_map(|x| x.get(name))
+    }
+
+    /// Names of every variable visible from the innermost scope
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.scopes
+            .iter()
+            .flat_map(|x| x.keys())
+            .map(String::as_str)
+    }
+
+    /// Declare a variable in the innermost scope, returning a warning when it shadows another one
+    pub fn declare(
+        &mut self,
+        name: &Identifier,
+        variable: Variable,
+    ) -> Result<Option<Diagnostic>, Report> {
+        let innermost = self.scopes.len() - 1;
+
+        if let Some(existing) = self.scopes[innermost].get(&name.name) {
+            return Err(Diagnostic::error(
+                format!("Variable \"{}\" is already declared", name.name),
+                name.span,
+            )
+            .with_related(
+                format!("\"{}\" is first declared here", name.name),
+                existing.declaration,
+            )
+            .into());
+        }
+
+        let warning = match self.lookup(&name.name) {
+            Some(outer) if outer.value.is_some() => {
+                return Err(Diagnostic::error(
+                    format!("Constant \"{}\" can't be shadowed", name.name),
+                    name.span,
+                )
+                .with_related(
+                    format!("\"{}\" is declared here", name.name),
+                    outer.declaration,
+                )
+                .into())
+            }
+            Some(outer) => Some(
+                Diagnostic::warning(
+                    format!(
+                        "Variable \"{}\" shadows the one declared on line {}",
+                        name.name, outer.declaration.line
+                    ),
+                    name.span,
+                )
+                .with_related(
+                    format!("\"{}\" is declared here", name.name),
+                    outer.declaration,
+                ),
+            ),
+            None => None,
+        };
+
+        self.scopes[innermost].insert(name.name.to_owned(), variable);
+
+        Ok(warning)
+    }
+}
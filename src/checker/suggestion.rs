@@ -0,0 +1,66 @@
+use crate::ast::Identifier;
+
+use super::diagnostic::Diagnostic;
+
+/// Maximum edit distance for a candidate to still be considered a typo of the input.
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+pub fn levenshtein(lhs: &str, rhs: &str) -> usize {
+    let lhs = lhs.chars().collect::<Vec<char>>();
+    let rhs = rhs.chars().collect::<Vec<char>>();
+
+    let mut prev_row = (0..=rhs.len()).collect::<Vec<usize>>();
+    let mut curr_row = vec![0; rhs.len() + 1];
+
+    for (i, lhs_char) in lhs.iter().enumerate() {
+        curr_row[0] = i + 1;
+
+        for (j, rhs_char) in rhs.iter().enumerate() {
+            let substitution_cost = usize::from(!lhs_char.eq_ignore_ascii_case(rhs_char));
+
+            curr_row[j + 1] = (prev_row[j] + substitution_cost)
+                .min(prev_row[j + 1] + 1)
+                .min(curr_row[j] + 1);
+        }
+
+        std::mem::swap(&mut prev_row, &mut curr_row);
+    }
+
+    prev_row[rhs.len()]
+}
+
+/// Get the closest candidate to `name`, if any of them is close enough to be a typo.
+pub fn closest_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (levenshtein(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE.min(name.len()))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Format a "did you mean" hint to be appended into an error message.
+pub fn did_you_mean<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> String {
+    match closest_match(name, candidates) {
+        Some(suggestion) => format!(" Did you mean '{suggestion}'?"),
+        None => "".to_string(),
+    }
+}
+
+/// Error about a name that doesn't exist, suggesting the closest candidate with a fix renaming it
+pub fn unknown_name<'a>(
+    message: String,
+    name: &Identifier,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Diagnostic {
+    match closest_match(&name.name, candidates) {
+        Some(suggestion) => {
+            Diagnostic::error(format!("{message} Did you mean '{suggestion}'?"), name.span)
+                .with_suggestion(format!("Change to '{suggestion}'"), name.span, suggestion)
+        }
+        None => Diagnostic::error(message, name.span),
+    }
+}
@@ -1,266 +1,699 @@
 use std::str::FromStr;
 
-use crate::checker::{compat_check, funcs::unwrap_inner, Checker};
-use color_eyre::eyre::{bail, eyre, Context, ContextCompat, Report};
-use pest::iterators::{Pair, Pairs};
+use crate::checker::{
+    compat_check, config::Coercion, const_fold, diagnostic::Diagnostic, suggestion, Checker,
+};
+use color_eyre::eyre::{bail, eyre, Report};
+
+use crate::ast::{self, BinaryOp, Expr, ExprKind, Identifier, Literal, Span, StringPart};
+use crate::autalonparser::{AutalonParser, Rule};
+use crate::builtin_package_definition::{
+    equivalent_function, get_fn_metadata, AliasMetadata, BuiltinPkgFunctions, FunctionArgsMetadata,
+    FunctionMetadata, TargetUI,
+};
+use crate::formatter;
+use pest::Parser;
+
+impl Checker {
+    /// Type of the expression. The expression and everything inside it gets annotated with its type.
+    #[tracing::instrument(skip_all)]
+    pub fn get_expr_returntype(&mut self, expr: &mut Expr) -> Result<&'static str, Report> {
+        let span = expr.span;
+        let returntype = match &mut expr.kind {
+            ExprKind::Literal(Literal::String(_)) => "string",
+            ExprKind::Literal(Literal::Number(_)) => "number",
+            ExprKind::Literal(Literal::Decimal(_)) => "decimal",
+            ExprKind::Literal(Literal::Bool(_)) => "bool",
+            ExprKind::Interpolation(parts) => {
+                for part in parts {
+                    if let StringPart::Expr(expr) = part {
+                        let part_type = self.get_expr_returntype(expr)?;
+                        compat_check::check_interpolation(part_type)?;
+                    }
+                }
 
-use crate::autalonparser::Rule;
+                "string"
+            }
 
-impl<'a> Checker<'a> {
-    #[tracing::instrument(skip(pair))]
-    pub fn get_pair_returntype(&mut self, pair: Pair<'a, Rule>) -> Result<&'a str, Report> {
-        match pair.as_rule() {
-            Rule::string => Ok("string"),
-            Rule::number => Ok("number"),
-            Rule::bool => Ok("bool"),
-            Rule::byoption_enum => Ok("byoption"),
+            ExprKind::Variable(identifier) => self.var_lookup(identifier)?,
+            ExprKind::EnumMember {
+                package,
+                name,
+                member,
+            } => {
+                if let Some(pkg) = package {
+                    check_package(pkg, span)?;
+                }
 
-            Rule::logic_op => self.get_logic_returntype(pair.into_inner()),
-            Rule::comp_op => self.get_comp_returntype(pair.into_inner()),
-            Rule::function_call => self.get_fnpair_returntype(pair),
-            Rule::member_access => self.get_member_returntype(pair),
+                self.get_enum_returntype(&name.name, &member.name, package.as_deref())?
+            }
 
-            Rule::array_access => todo!(),
+            ExprKind::Call {
+                package,
+                name,
+                args,
+                builtin,
+            } => {
+                let Some(pkg) = package else {
+                    bail!("Local package name is currently unsupported for now")
+                };
+                check_package(pkg, span)?;
+
+                let resolved_name = match get_pkgfn_alias(&name.name, pkg) {
+                    Some(alias) => {
+                        self.resolve_alias(span, pkg, name, args, &alias)?;
+                        alias.replacement
+                    }
+                    None => &name.name,
+                };
+
+                let parsed_args = self.get_args_returntype(args)?;
+
+                let resolved = Identifier {
+                    name: resolved_name.to_string(),
+                    span: name.span,
+                };
+                let fn_enum = get_pkgfn(&resolved, pkg)?;
+                let function_metadata = get_fn_metadata(&fn_enum);
+
+                // Optional arguments left out are given their default when lowering
+                let omitted = function_metadata.args.get(args.len()..).unwrap_or_default();
+                let given_types = match omitted.iter().all(|x| x.optional) {
+                    true => parsed_args
+                        .iter()
+                        .copied()
+                        .chain(omitted.iter().map(|x| x.arg_type))
+                        .collect(),
+                    false => parsed_args.clone(),
+                };
+
+                if let Err(err) = check_fn_args(resolved_name, &function_metadata, &given_types) {
+                    // Parameters that took numbers as strings in older versions
+                    if let Some(unquoted) = unquote_numbers(args, &parsed_args, &function_metadata)
+                    {
+                        let unquoted_call = Expr::new(
+                            ExprKind::Call {
+                                package: Some(pkg.to_owned()),
+                                name: resolved,
+                                args: unquoted,
+                                builtin: None,
+                            },
+                            span,
+                        );
+
+                        return Err(Diagnostic::error(format!("{err:#}"), span)
+                            .with_fix(
+                                "Write the numbers without quotes",
+                                span,
+                                formatter::format_expr(&unquoted_call),
+                            )
+                            .into());
+                    }
+
+                    let Some(fitted) = fit_args(args, &parsed_args, &function_metadata, span)
+                    else {
+                        return Err(err);
+                    };
+
+                    let fitted_call = Expr::new(
+                        ExprKind::Call {
+                            package: Some(pkg.to_owned()),
+                            name: resolved,
+                            args: fitted.args,
+                            builtin: None,
+                        },
+                        span,
+                    );
+                    let diagnostic = Diagnostic::error(format!("{err:#}"), span);
+                    let title = format!("Match the arguments of '{resolved_name}'");
+                    let replacement = formatter::format_expr(&fitted_call);
+
+                    // A guess of which argument goes where needs a review
+                    return Err(match fitted.certain {
+                        true => diagnostic.with_fix(title, span, replacement),
+                        false => diagnostic.with_suggestion(title, span, replacement),
+                    }
+                    .into());
+                }
+                self.check_target_ui(name, fn_enum, &function_metadata)?;
+                self.check_driver_version(name, &function_metadata)?;
 
-            Rule::comparable => self.get_pair_returntype(unwrap_inner(pair)?),
-            Rule::atomic_expression => self.get_pair_returntype(unwrap_inner(pair)?),
-            Rule::basic_expr => self.get_pair_returntype(unwrap_inner(pair)?),
+                // The enum name, since templates are looked up by it
+                *builtin = Some(fn_enum.into());
+                function_metadata.return_type
+            }
+            ExprKind::MethodCall {
+                receiver,
+                name,
+                args,
+            } => {
+                let parsed_args = self.get_args_returntype(args)?;
+                let val_type = self.get_expr_returntype(receiver)?;
+
+                self.get_method_returntype(val_type, &name.name, parsed_args)?
+            }
 
-            nonmatch => Err(eyre!("{nonmatch:?} doesn't match any return type!")),
-        }
-    }
+            ExprKind::Unary { op, operand } => {
+                let operand_type = self.get_expr_returntype(operand)?;
+                compat_check::check_unary_op(operand_type, *op)?;
 
-    #[tracing::instrument(skip(pairs))]
-    pub fn get_expr_returntype(&mut self, pairs: Pairs<'a, Rule>) -> Result<&'a str, Report> {
-        use pest::pratt_parser::{Assoc, Op, PrattParser};
-        let parser = PrattParser::new()
-            .op(Op::infix(Rule::add_op, Assoc::Left) | Op::infix(Rule::sub_op, Assoc::Left))
-            .op(Op::infix(Rule::div_op, Assoc::Left) | Op::infix(Rule::mul_op, Assoc::Left));
-
-        parser
-            .map_primary(|x| self.get_pair_returntype(x))
-            .map_infix(|lhs, op, rhs| {
-                // Bubble up any errors from left/right side
-                let lhs = lhs?;
-                let rhs = rhs?;
-
-                // Check if lhs type is the same as rhs
-                if lhs != rhs {
-                    return Err(eyre!("'{lhs}' type doesn't match with '{rhs}'"));
-                }
+                // Negating keeps the number, inverting keeps the bool
+                operand_type
+            }
+            ExprKind::Binary { op, lhs, rhs } => self.get_binary_returntype(*op, lhs, rhs)?,
+            ExprKind::Index { .. } => bail!("Array access is not supported yet"),
+        };
 
-                // Check if current operator is compatible with right hand side
-                compat_check::check_arithmetic_op(rhs, op.as_rule())?;
+        expr.ty = Some(returntype);
 
-                Ok(lhs)
-            })
-            .parse(pairs)
+        Ok(returntype)
     }
 
-    #[tracing::instrument(skip(comparable))]
-    pub fn get_comparable_returntype(
+    #[tracing::instrument(skip(self, lhs, rhs))]
+    fn get_binary_returntype(
         &mut self,
-        comparable: Pair<'a, Rule>,
-    ) -> Result<&'a str, Report> {
-        match comparable.as_rule() {
-            Rule::string => Ok("string"),
-            Rule::number => Ok("number"),
-            Rule::bool => Ok("bool"),
-            Rule::byoption_enum => Ok("byoption"),
-
-            nonmatch => Err(eyre!("'{nonmatch:?}' doesn't match any return type!")),
+        op: BinaryOp,
+        lhs: &mut Expr,
+        rhs: &mut Expr,
+    ) -> Result<&'static str, Report> {
+        // Integers mixed with decimals are computed as decimals
+        let (lhs_type, rhs_type) = compat_check::promote_numeric(
+            self.get_expr_returntype(lhs)?,
+            self.get_expr_returntype(rhs)?,
+        );
+
+        // Logic operators only work on booleans
+        if op.is_logic() {
+            for operand in [lhs_type, rhs_type] {
+                if operand != "bool" {
+                    bail!("Type '{operand}' is incompatible with '{op}'!");
+                }
+            }
+
+            return Ok("bool");
         }
-    }
 
-    #[tracing::instrument(skip(logic_pairs))]
-    pub fn get_logic_returntype(
-        &mut self,
-        logic_pairs: Pairs<'a, Rule>,
-    ) -> Result<&'a str, Report> {
-        use pest::pratt_parser::{Assoc, Op, PrattParser};
-        let parser = PrattParser::new()
-            .op(Op::infix(Rule::and_op, Assoc::Left) | Op::infix(Rule::or_op, Assoc::Left));
+        if op == BinaryOp::Add && (lhs_type == "string" || rhs_type == "string") {
+            return self.get_concat_returntype(lhs_type, rhs_type);
+        }
 
-        parser
-            .map_primary(|primary| match primary.as_rule() {
-                Rule::comp_op => self.get_comp_returntype(primary.into_inner()),
+        // Check if lhs type is the same as rhs
+        if lhs_type != rhs_type {
+            bail!("'{lhs_type}' type doesn't match with '{rhs_type}'");
+        }
 
-                _ => self.get_comparable_returntype(primary),
-            })
-            .map_infix(|lhs, _op, rhs| {
-                // Bubble up any errors from left/right side
-                let lhs = lhs?;
-                let _rhs = rhs?;
+        if op.is_comparison() {
+            compat_check::check_comparation_op(rhs_type, op)?;
 
-                Ok(lhs)
-            })
-            .parse(logic_pairs)
+            // Comparing always results in a boolean
+            return Ok("bool");
+        }
+
+        // Check if current operator is compatible with right hand side
+        compat_check::check_arithmetic_op(rhs_type, op)?;
+
+        let constant = |x: &str| self.scopes.lookup(x).and_then(|x| x.value.clone());
+        if let Some(warning) = const_fold::binary_warning(op, lhs, rhs, &constant) {
+            self.warnings
+                .push(Diagnostic::warning(warning, lhs.span.to(rhs.span)));
+        }
+
+        Ok(lhs_type)
     }
 
-    #[tracing::instrument(skip(comp_op_pairs))]
-    pub fn get_comp_returntype(
-        &mut self,
-        comp_op_pairs: Pairs<'a, Rule>,
-    ) -> Result<&'a str, Report> {
-        use pest::pratt_parser::{Assoc, Op, PrattParser};
-        let parser = PrattParser::new()
-            .op(Op::infix(Rule::ne_op, Assoc::Left) | Op::infix(Rule::eq_op, Assoc::Left))
-            .op(Op::infix(Rule::ge_op, Assoc::Left) | Op::infix(Rule::le_op, Assoc::Left))
-            .op(Op::infix(Rule::gt_op, Assoc::Left) | Op::infix(Rule::lt_op, Assoc::Left));
-
-        parser
-            .map_primary(|x| self.get_comparable_returntype(x))
-            .map_infix(|lhs, op, rhs| {
-                // Bubble up any errors from left/right side
-                let lhs_type = lhs?;
-                let rhs_type = rhs?;
-
-                // Check if lhs type is the same as rhs
-                if lhs_type != rhs_type {
-                    return Err(eyre!("'{lhs_type}' type doesn't match with '{rhs_type}'"));
-                }
+    /// Adding anything to a string, allowed for plain values when the coercion policy says so
+    fn get_concat_returntype(
+        &self,
+        lhs_type: &'static str,
+        rhs_type: &'static str,
+    ) -> Result<&'static str, Report> {
+        let other = if lhs_type == "string" {
+            rhs_type
+        } else {
+            lhs_type
+        };
 
-                // Add comparation operation compatibility
-                compat_check::check_comparation_op(rhs_type, op.as_rule())?;
+        match (other, self.config.coercion) {
+            ("string", _) => (),
+            ("number" | "decimal" | "bool", Coercion::Concat) => (),
+            ("number" | "decimal" | "bool", Coercion::Strict) => bail!(
+                "'{lhs_type}' type doesn't match with '{rhs_type}'. Convert it with 'toString()', or allow it with #[coercion=concat]"
+            ),
+            _ => bail!("'{lhs_type}' type doesn't match with '{rhs_type}'"),
+        }
 
-                Ok(lhs_type)
-            })
-            .parse(comp_op_pairs)
+        Ok("string")
     }
 
-    fn get_fnpair_returntype(&mut self, pair: Pair<'a, Rule>) -> Result<&'a str, Report> {
-        if pair.as_rule() != Rule::function_call {
+    /// Rewrite the arguments of a deprecated alias call into the ones of its replacement,
+    /// warning about the deprecation with a fix that calls the replacement
+    fn resolve_alias(
+        &mut self,
+        span: Span,
+        package: &str,
+        name: &Identifier,
+        args: &mut Vec<Expr>,
+        alias: &AliasMetadata,
+    ) -> Result<(), Report> {
+        let alias_arg_index = |x: &str| {
+            x.strip_prefix("{arg")?
+                .strip_suffix('}')?
+                .parse::<usize>()
+                .ok()?
+                .checked_sub(1)
+        };
+
+        let expected = alias
+            .args
+            .iter()
+            .filter(|x| alias_arg_index(x).is_some())
+            .count();
+        if args.len() != expected {
             bail!(
-                "Pair is not a function. Received input: {:?}",
-                pair.as_rule()
-            )
+            "Argument supplied for function \"{}\" didn't match. Supplied argument count \"{}\", expected argument count \"{}\"",
+            name.name, args.len(), expected)
         }
 
-        let mut inner_pair = pair.into_inner();
-        let member = inner_pair
-            .next()
-            .context("Can't get member or function name!")?;
-        let args = inner_pair.next();
-
-        let fn_tokens = member.into_inner().into_iter().collect::<Vec<Pair<Rule>>>();
-        let (pkg_name, val_name, fn_name) = match fn_tokens.len() {
-            3 => (
-                Some(unwrap_inner(fn_tokens[0].clone())?),
-                Some(&fn_tokens[1]),
-                &fn_tokens[2],
-            ),
-            2 => {
-                if fn_tokens[0].as_rule() == Rule::package {
-                    (
-                        Some(unwrap_inner(fn_tokens[0].clone())?),
-                        None,
-                        &fn_tokens[1],
-                    )
-                } else {
-                    (None, Some(&fn_tokens[0]), &fn_tokens[1])
-                }
-            }
-            1 => (None, None, &fn_tokens[0]),
-            _ => bail!(
-                "Invalid function token length! Expected token count: 3; Received token count: {}",
-                fn_tokens.len()
+        let alias_args = std::mem::take(args);
+        *args = alias
+            .args
+            .iter()
+            .map(|x| match alias_arg_index(x) {
+                Some(i) => alias_args
+                    .get(i)
+                    .cloned()
+                    .ok_or_else(|| eyre!("Alias \"{}\" has no argument {x}", alias.name)),
+                None => ast::parse_expr_at(x, span),
+            })
+            .collect::<Result<Vec<Expr>, Report>>()?;
+
+        let replacement_call = Expr::new(
+            ExprKind::Call {
+                package: Some(package.to_string()),
+                name: Identifier {
+                    name: alias.replacement.to_string(),
+                    span: name.span,
+                },
+                args: args.clone(),
+                builtin: None,
+            },
+            span,
+        );
+        self.warnings.push(
+            Diagnostic::warning(
+                format!(
+                    "\"{}\" is deprecated since {}, use \"{}\" instead",
+                    name.name, alias.since_version, alias.replacement
+                ),
+                name.span,
+            )
+            .with_fix(
+                format!("Replace with '{}'", alias.replacement),
+                span,
+                formatter::format_expr(&replacement_call),
             ),
-        };
+        );
+
+        Ok(())
+    }
 
-        let parsed_args = match args {
-            None => vec![],
-            Some(outer_pair) => outer_pair
-                .into_inner() // Get inners
-                .into_iter() // Into iterable
-                .map(unwrap_inner) // Get args inner pair
-                .collect::<Result<Vec<Pair<Rule>>, Report>>()? // Collect and get any errors
-                .into_iter() // Into iterable again
-                .map(|pair| pair.into_inner()) // Get all pairs inside iterated pair
-                .map(|x| self.get_expr_returntype(x)) // Get iterated pair return type using all pairs inside
-                .collect::<Result<Vec<&str>, Report>>()?, // Collect, and get any errors
+    /// Builtins made for another UI framework than the declared one can't be called
+    fn check_target_ui(
+        &self,
+        name: &Identifier,
+        fn_enum: BuiltinPkgFunctions,
+        function_metadata: &FunctionMetadata,
+    ) -> Result<(), Report> {
+        let Some(declared) = self.config.ui else {
+            return Ok(());
         };
+        let target_ui = function_metadata.target_ui;
 
-        let result = match pkg_name {
-            None => bail!("Local package name is currently unsupported for now"),
-            Some(pkg) => match val_name {
-                Some(_) => bail!("Builtin value is currently unsupported for now"),
-                None => self.get_pkgfn_returntype(fn_name.as_str(), pkg.as_str(), parsed_args)?,
-            },
+        if target_ui == TargetUI::Any || target_ui == declared {
+            return Ok(());
+        }
+
+        let hint = match equivalent_function(fn_enum.into(), target_ui, declared) {
+            Some(equivalent) => format!(" Did you mean '{equivalent}'?"),
+            None => format!(" It has no {declared} equivalent."),
         };
 
-        Ok(result)
+        Err(Diagnostic::error(
+            format!(
+                "\"{}\" is made for {target_ui}, but the script declares #[ui={}].{hint}",
+                name.name,
+                declared.to_string().to_lowercase()
+            ),
+            name.span,
+        )
+        .into())
     }
 
-    #[tracing::instrument(skip(pair))]
-    pub fn get_member_returntype(&mut self, pair: Pair<'a, Rule>) -> Result<&'a str, Report> {
-        if pair.as_rule() != Rule::function_call {
-            bail!(
-                "Pair is not a function. Received input: {:?}",
-                pair.as_rule()
-            )
-        }
+    /// Builtins added in a newer driver than the one the script is pinned to can't be called
+    fn check_driver_version(
+        &self,
+        name: &Identifier,
+        function_metadata: &FunctionMetadata,
+    ) -> Result<(), Report> {
+        let since_driver = function_metadata.since_driver;
 
-        let inner_pair = pair.into_inner();
-        let tokens = inner_pair.into_iter().collect::<Vec<Pair<Rule>>>();
+        if since_driver <= self.config.driver {
+            return Ok(());
+        }
 
-        let (pkg_name, val_name) = match tokens.len() {
-            2 => (Some(unwrap_inner(tokens[0].clone())?), &tokens[1]),
-            1 => (None, &tokens[1]),
-            _ => bail!(
-                "Invalid function token length! Expected token count: 3; Received token count: {}",
-                tokens.len()
+        Err(Diagnostic::error(
+            format!(
+                "\"{}\" needs driver {since_driver} or newer, but the script declares #[driver_ver={}]",
+                name.name, self.config.driver
             ),
-        };
-
-        let result = match pkg_name {
-            Some(_) => bail!("Package name other than builtin ('#') is currently not supported"),
-            None => self.var_lookup(val_name.as_str())?,
-        };
+            name.span,
+        )
+        .into())
+    }
 
-        Ok(result)
+    fn get_args_returntype(&mut self, args: &mut [Expr]) -> Result<Vec<&'static str>, Report> {
+        args.iter_mut()
+            .map(|x| self.get_expr_returntype(x))
+            .collect::<Result<Vec<&str>, Report>>()
     }
 
-    #[tracing::instrument]
-    pub fn get_pkgfn_returntype(
+    #[tracing::instrument(skip(self))]
+    pub fn get_enum_returntype(
         &mut self,
-        name: &'a str,
-        pkg: &'a str,
-        args: Vec<&'a str>,
-    ) -> Result<&'a str, Report> {
-        use crate::builtin_package_definition::{get_fn_metadata, BuiltinPkgFunctions};
-
+        name: &str,
+        member: &str,
+        pkg: Option<&str>,
+    ) -> Result<&'static str, Report> {
+        use crate::builtin_package_definition::{get_enum_metadata, BuiltinPkgEnums};
+        use strum::IntoEnumIterator;
+
+        // Enums without a package prefix are resolved from the builtin package
         // TODO: Remove hardcoded package alias switching
-        let pkg = if pkg == "#" { "builtin" } else { pkg };
+        let pkg = match pkg {
+            None | Some("#") => "builtin",
+            Some(pkg) => pkg,
+        };
 
-        let function_metadata = match pkg {
+        let enum_metadata = match pkg {
             "builtin" => {
-                let fn_enum = BuiltinPkgFunctions::from_str(name)
-                    .context(format!("Failed to match enum {name}"))?;
-                get_fn_metadata(&fn_enum)
+                let enum_names = BuiltinPkgEnums::iter()
+                    .map(|x| get_enum_metadata(&x).name)
+                    .collect::<Vec<&str>>();
+
+                let enum_kind = BuiltinPkgEnums::from_str(name).map_err(|_| {
+                    eyre!(
+                        "Enum '{name}' doesn't exist!{}",
+                        suggestion::did_you_mean(name, enum_names)
+                    )
+                })?;
+                get_enum_metadata(&enum_kind)
             }
 
             _str => bail!("Package other than builtin is not implemented yet"),
         };
 
-        if function_metadata.args.len() != args.len() {
+        if !enum_metadata.members.iter().any(|x| x.name == member) {
             bail!(
-            "Argument supplied for function \"{}\" didn't match. Supplied argument count \"{}\", expected argument count \"{}\"",
-            name, args.len(), function_metadata.args.len())
+                "'{member}' is not a member of enum '{}'.{}",
+                enum_metadata.name,
+                suggestion::did_you_mean(member, enum_metadata.members.iter().map(|x| x.name))
+            )
         }
 
-        let zipped_args = args
+        Ok(enum_metadata.type_name)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn get_pkgfn_returntype(
+        &mut self,
+        name: &Identifier,
+        pkg: &str,
+        args: Vec<&str>,
+    ) -> Result<&'static str, Report> {
+        let function_metadata = get_fn_metadata(&get_pkgfn(name, pkg)?);
+
+        check_fn_args(&name.name, &function_metadata, &args)?;
+
+        Ok(function_metadata.return_type)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn get_method_returntype(
+        &mut self,
+        val_type: &str,
+        name: &str,
+        args: Vec<&str>,
+    ) -> Result<&'static str, Report> {
+        use crate::builtin_package_definition::{get_element_method_metadata, ElementMethods};
+        use strum::IntoEnumIterator;
+
+        let method_metadata = match val_type {
+            "element" => {
+                let method_names = ElementMethods::iter()
+                    .map(|x| get_element_method_metadata(&x).name)
+                    .collect::<Vec<&str>>();
+
+                let method = ElementMethods::from_str(name).map_err(|_| {
+                    eyre!(
+                        "Type 'element' doesn't have method '{name}'.{}",
+                        suggestion::did_you_mean(name, method_names)
+                    )
+                })?;
+                get_element_method_metadata(&method)
+            }
+
+            "string" | "number" | "decimal" | "bool" => {
+                use crate::builtin_package_definition::{
+                    get_conversion_method_metadata, get_conversion_source_types, ConversionMethods,
+                };
+
+                let method_names = ConversionMethods::iter()
+                    .map(|x| get_conversion_method_metadata(&x).name)
+                    .collect::<Vec<&str>>();
+
+                let method = ConversionMethods::from_str(name).map_err(|_| {
+                    eyre!(
+                        "Type '{val_type}' doesn't have method '{name}'.{}",
+                        suggestion::did_you_mean(name, method_names)
+                    )
+                })?;
+                if !get_conversion_source_types(&method).contains(&val_type) {
+                    bail!("Type '{val_type}' can't be converted with '{name}'")
+                }
+
+                get_conversion_method_metadata(&method)
+            }
+
+            other => bail!("Type '{other}' doesn't have any methods"),
+        };
+
+        check_fn_args(name, &method_metadata, &args)?;
+
+        Ok(method_metadata.return_type)
+    }
+}
+
+fn get_pkgfn_alias(name: &str, pkg: &str) -> Option<AliasMetadata> {
+    use crate::builtin_package_definition::{get_alias_metadata, BuiltinPkgAliases};
+
+    match pkg {
+        "#" | "builtin" => BuiltinPkgAliases::from_str(name)
+            .ok()
+            .map(|x| get_alias_metadata(&x)),
+        _ => None,
+    }
+}
+
+/// Packages calls and enums can be taken from
+const PACKAGES: [&str; 2] = ["builtin", "#"];
+
+/// The package prefix starts the call or enum member `span`
+fn check_package(pkg: &str, span: Span) -> Result<(), Report> {
+    if PACKAGES.contains(&pkg) {
+        return Ok(());
+    }
+
+    let package = Identifier {
+        name: pkg.to_string(),
+        span: Span {
+            end: span.start + pkg.len(),
+            ..span
+        },
+    };
+    Err(suggestion::unknown_name(
+        format!("Package '{pkg}' doesn't exist!"),
+        &package,
+        PACKAGES,
+    )
+    .into())
+}
+
+fn get_pkgfn(name: &Identifier, pkg: &str) -> Result<BuiltinPkgFunctions, Report> {
+    use strum::IntoEnumIterator;
+
+    // TODO: Remove hardcoded package alias switching
+    let pkg = if pkg == "#" { "builtin" } else { pkg };
+
+    match pkg {
+        "builtin" => BuiltinPkgFunctions::from_str(&name.name).map_err(|_| {
+            suggestion::unknown_name(
+                format!("Function '{}' doesn't exist!", name.name),
+                name,
+                BuiltinPkgFunctions::iter().map(<&str>::from),
+            )
+            .into()
+        }),
+
+        _str => bail!("Package other than builtin is not implemented yet"),
+    }
+}
+
+/// Default value of a parameter as an expression put at `span`
+pub fn default_arg(param: &FunctionArgsMetadata, span: Span) -> Result<Expr, Report> {
+    match param.arg_type {
+        // String defaults are stored without their quotes
+        "string" => Ok(Expr::new(
+            ExprKind::Literal(Literal::String(param.default_value.to_string())),
+            span,
+        )),
+        _ => ast::parse_expr_at(param.default_value, span),
+    }
+}
+
+/// Arguments of a call rearranged to fit the parameters of its function
+struct FittedArgs {
+    args: Vec<Expr>,
+    /// Whether the arguments can't go anywhere else: they keep their positions with defaults
+    /// added after them, or every argument fits a single parameter by type
+    certain: bool,
+}
+
+/// Arguments of a call that doesn't match its function, rearranged so every parameter takes the
+/// first unused argument of its type, or its default value. `None` when they can't fit.
+fn fit_args(
+    args: &[Expr],
+    arg_types: &[&str],
+    function_metadata: &FunctionMetadata,
+    span: Span,
+) -> Option<FittedArgs> {
+    let mut unused = (0..args.len()).collect::<Vec<usize>>();
+    let mut taken = vec![];
+    let mut fitted = vec![];
+
+    for param in &function_metadata.args {
+        let fitting = unused
             .iter()
-            .zip(function_metadata.args.iter().map(|x| x.arg_type))
-            .collect::<Vec<(&&str, &str)>>();
-
-        for (i, (arg, fn_metadata_arg)) in zipped_args.iter().enumerate() {
-            if arg != &fn_metadata_arg {
-                bail!(
-                "Argument supplied for function \"{}\" didn't match. Supplied argument type for position {} is \"{}\", expected argument type for position {} is \"{}\"",
-                name, i, arg, i, fn_metadata_arg)
+            .position(|&i| compat_check::is_assignable(arg_types[i], param.arg_type));
+
+        let arg = match fitting {
+            Some(position) => {
+                let i = unused.remove(position);
+                taken.push(Some(i));
+                args[i].clone()
+            }
+            None if param.default_value.is_empty() => return None,
+            None => {
+                taken.push(None);
+                default_arg(param, span).ok()?
             }
+        };
+        fitted.push(arg);
+    }
+
+    if !unused.is_empty() {
+        return None;
+    }
+
+    let in_place = taken
+        .iter()
+        .take(args.len())
+        .enumerate()
+        .all(|(slot, i)| *i == Some(slot));
+    let unambiguous = arg_types.iter().all(|arg_type| {
+        function_metadata
+            .args
+            .iter()
+            .filter(|x| compat_check::is_assignable(arg_type, x.arg_type))
+            .count()
+            == 1
+    });
+
+    Some(FittedArgs {
+        args: fitted,
+        certain: in_place || unambiguous,
+    })
+}
+
+/// Arguments of a call with the string literals given to number parameters written as numbers.
+/// `None` when that doesn't make them match the function.
+fn unquote_numbers(
+    args: &[Expr],
+    arg_types: &[&'static str],
+    function_metadata: &FunctionMetadata,
+) -> Option<Vec<Expr>> {
+    let mut unquoted = args.to_vec();
+    let mut unquoted_types = arg_types.to_vec();
+
+    for ((arg, arg_type), param) in unquoted
+        .iter_mut()
+        .zip(unquoted_types.iter_mut())
+        .zip(&function_metadata.args)
+    {
+        let ExprKind::Literal(Literal::String(text)) = &arg.kind else {
+            continue;
+        };
+        if compat_check::is_assignable(arg_type, param.arg_type) {
+            continue;
         }
 
-        Ok(function_metadata.return_type)
+        *arg = number_literal(text, arg.span)?;
+        *arg_type = match arg.kind {
+            ExprKind::Literal(Literal::Decimal(_)) => "decimal",
+            _ => "number",
+        };
     }
+
+    let changed = unquoted_types != arg_types;
+    (changed && check_fn_args(function_metadata.name, function_metadata, &unquoted_types).is_ok())
+        .then_some(unquoted)
+}
+
+/// Number literal written exactly as `text`, if it is one
+fn number_literal(text: &str, span: Span) -> Option<Expr> {
+    let pair = AutalonParser::parse(Rule::number, text).ok()?.next()?;
+    if pair.as_str() != text {
+        return None;
+    }
+
+    let mut expr = ast::build_expr(pair).ok()?;
+    expr.span = span;
+
+    Some(expr)
+}
+
+fn check_fn_args(
+    name: &str,
+    function_metadata: &FunctionMetadata,
+    args: &[&str],
+) -> Result<(), Report> {
+    if function_metadata.args.len() != args.len() {
+        bail!(
+        "Argument supplied for function \"{}\" didn't match. Supplied argument count \"{}\", expected argument count \"{}\"",
+        name, args.len(), function_metadata.args.len())
+    }
+
+    let zipped_args = args
+        .iter()
+        .zip(function_metadata.args.iter().map(|x| x.arg_type))
+        .collect::<Vec<(&&str, &str)>>();
+
+    for (i, (arg, fn_metadata_arg)) in zipped_args.iter().enumerate() {
+        if !compat_check::is_assignable(arg, fn_metadata_arg) {
+            bail!(
+            "Argument supplied for function \"{}\" didn't match. Supplied argument type for position {} is \"{}\", expected argument type for position {} is \"{}\"",
+            name, i, arg, i, fn_metadata_arg)
+        }
+    }
+
+    Ok(())
 }
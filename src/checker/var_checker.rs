@@ -1,91 +1,156 @@
-use std::collections::{hash_map::Entry, HashMap};
-
-use crate::checker::{funcs::unwrap_inner, Checker};
-use color_eyre::eyre::{bail, Report};
-use pest::iterators::Pair;
-
-use crate::autalonparser::Rule;
-
-impl<'a> Default for Checker<'a> {
+use crate::checker::{
+    compat_check,
+    config::CheckerConfig,
+    const_fold,
+    diagnostic::Diagnostic,
+    scope::{ScopeStack, Variable},
+    suggestion, Checker,
+};
+use color_eyre::eyre::{bail, eyre, Report};
+
+use crate::ast::{Expr, Identifier, Statement, StatementKind};
+
+impl Default for Checker {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<'a> Checker<'a> {
+impl Checker {
+    #[tracing::instrument]
+    pub fn new() -> Checker {
+        Checker::with_config(CheckerConfig::default())
+    }
+
     #[tracing::instrument]
-    pub fn new() -> Checker<'a> {
+    pub fn with_config(config: CheckerConfig) -> Checker {
         Checker {
-            var_table: HashMap::new(),
+            scopes: ScopeStack::new(),
+            config,
+            warnings: vec![],
         }
     }
 }
 
-impl<'a> Checker<'a> {
+impl Checker {
+    /// Check a statement, annotating the types of its expressions
     #[tracing::instrument(skip_all)]
-    pub fn check_var_declaration(&mut self, pair: Pair<'a, Rule>) -> Result<(), Report> {
-        if pair.as_rule() != Rule::var_declaration {
-            bail!("Pair is not a variable declaration");
-        }
-
-        let inner_pair = pair;
-        let mut inner_pair = unwrap_inner(inner_pair)?.into_inner();
-
-        let var_identifier = inner_pair.next().expect("Can't get variable identifier");
-        let var_expr = inner_pair.next().expect("Can't get variable expression");
-
-        let var_expression_type = self.get_expr_returntype(var_expr.into_inner())?;
-
-        self.var_insert(var_identifier.as_str(), var_expression_type)?;
+    pub fn check_statement(&mut self, statement: &mut Statement) -> Result<(), Report> {
+        match &mut statement.kind {
+            StatementKind::Expr(expr) => {
+                self.get_expr_returntype(expr)?;
+            }
+            StatementKind::VarDeclaration {
+                name,
+                value,
+                constant,
+            } => {
+                self.check_var_declaration(name, value, *constant)?;
+            }
+            StatementKind::VarAssignment { name, value } => {
+                self.check_var_assignment(name, value)?;
+            }
+            StatementKind::Escape(_) => (),
+        };
 
         Ok(())
     }
 
     #[tracing::instrument(skip_all)]
-    pub fn check_var_assignment(&mut self, pair: Pair<'a, Rule>) -> Result<(), Report> {
-        if pair.as_rule() != Rule::var_assignment {
-            bail!("Pair is not a variable declaration")
-        }
-
-        // Reassigning to scope into inner
-        let mut inner_pair = pair.into_inner();
-
-        let var_identifier = inner_pair.next().expect("Can't get variable identifier");
-        let var_expr = inner_pair.next().expect("Can't get variable expression");
-
-        let var_expr_type = self.get_expr_returntype(var_expr.into_inner())?;
+    pub fn check_var_declaration(
+        &mut self,
+        name: &Identifier,
+        value: &mut Expr,
+        constant: bool,
+    ) -> Result<(), Report> {
+        let var_expression_type = self.get_expr_returntype(value)?;
+
+        // Constants have to be known when the script is transpiled, so cfg sections can use them
+        let value = match constant {
+            false => None,
+            true => Some(
+                const_fold::evaluate(value, &|x| self.scopes.lookup(x)?.value.clone()).ok_or_else(
+                    || {
+                        eyre!(
+                            "Value of constant \"{}\" must be made of literals and other constants",
+                            name.name
+                        )
+                    },
+                )?,
+            ),
+        };
+
+        self.var_insert(
+            name,
+            Variable {
+                var_type: var_expression_type,
+                declaration: name.span,
+                value,
+            },
+        )
+    }
 
-        let current_var_type = self.var_lookup(var_identifier.as_str())?;
+    #[tracing::instrument(skip_all)]
+    pub fn check_var_assignment(
+        &mut self,
+        name: &Identifier,
+        value: &mut Expr,
+    ) -> Result<(), Report> {
+        let var_expr_type = self.get_expr_returntype(value)?;
+
+        let current_var_type = self.var_lookup(name)?;
+
+        if let Some(variable) = self.scopes.lookup(&name.name).filter(|x| x.value.is_some()) {
+            return Err(Diagnostic::error(
+                format!("Cannot assign to constant \"{}\"", name.name),
+                name.span,
+            )
+            .with_related(
+                format!("\"{}\" is declared here", name.name),
+                variable.declaration,
+            )
+            .into());
+        }
 
-        if current_var_type != var_expr_type {
+        if !compat_check::is_assignable(var_expr_type, current_var_type) {
             bail!(
                 "Expression type assigned to variable \"{}\" didn't match",
-                var_identifier.as_str().to_string()
+                name.name
             )
         } else {
             Ok(())
         }
     }
 
+    /// Start a nested scope, for the statements of a block
+    pub fn enter_scope(&mut self) {
+        self.scopes.push();
+    }
+
+    /// End the innermost scope, dropping the variables declared in it
+    pub fn exit_scope(&mut self) {
+        self.scopes.pop();
+    }
+
     #[tracing::instrument(skip(self))]
-    pub fn var_insert(&mut self, name: &'a str, vartype: &'a str) -> Result<(), Report> {
-        let entry = self.var_table.entry(name);
-        match entry {
-            Entry::Occupied(_) => bail!("Variable already exists!"),
-            Entry::Vacant(_) => {
-                self.var_table.insert(name, vartype);
-                Ok(())
-            }
+    pub fn var_insert(&mut self, name: &Identifier, variable: Variable) -> Result<(), Report> {
+        if let Some(warning) = self.scopes.declare(name, variable)? {
+            self.warnings.push(warning);
         }
+
+        Ok(())
     }
 
     #[tracing::instrument(skip(self))]
-    pub fn var_lookup(&mut self, name: &'a str) -> Result<&'a str, Report> {
-        let entry = self.var_table.entry(name);
-        if let Entry::Occupied(keyval) = entry {
-            Ok(keyval.get())
-        } else {
-            bail!("Variable doesn't exist!")
+    pub fn var_lookup(&mut self, name: &Identifier) -> Result<&'static str, Report> {
+        match self.scopes.lookup(&name.name) {
+            Some(variable) => Ok(variable.var_type),
+            None => Err(suggestion::unknown_name(
+                format!("Variable \"{}\" doesn't exist!", name.name),
+                name,
+                self.scopes.names(),
+            )
+            .into()),
         }
     }
 }
@@ -0,0 +1,129 @@
+pub mod code_action;
+pub mod completion;
+pub mod hover;
+pub mod scanner;
+pub mod signature;
+pub mod symbols;
+
+use serde::Serialize;
+use strum::IntoEnumIterator;
+
+use crate::builtin_package_definition::{
+    get_conversion_method_metadata, get_conversion_source_types, get_element_method_metadata,
+    get_enum_metadata, get_fn_metadata, BuiltinPkgEnums, BuiltinPkgFunctions, ConversionMethods,
+    ElementMethods, EnumMetadata, FunctionMetadata, TargetUI,
+};
+use crate::checker::{config::CheckerConfig, const_fold};
+
+pub use code_action::code_actions;
+pub use completion::complete;
+pub use hover::hover;
+pub use signature::signature_help;
+pub use symbols::definition;
+
+/// A range of the source code, in byte offsets
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Builtin functions with the name they're called by, which can differ from their metadata name
+pub(crate) fn builtin_functions() -> impl Iterator<Item = (&'static str, FunctionMetadata)> {
+    BuiltinPkgFunctions::iter().map(|x| (x.into(), get_fn_metadata(&x)))
+}
+
+/// Checking options the script declares in its cfg sections, read like the checker does.
+/// Keeps working while the script is being edited: constants of the statements that don't
+/// parse yet are unknown, and options with an unknown value keep their default.
+pub fn declared_config(code: &str) -> CheckerConfig {
+    let statements = symbols::parse_statements(code);
+    let constants = const_fold::program_constants(statements.iter().map(|(_, x)| x));
+
+    CheckerConfig::from_cfg(&symbols::cfg_entries(code), &constants).0
+}
+
+/// Builtin functions usable with the UI framework and driver version the script declares
+pub(crate) fn available_builtin_functions(
+    code: &str,
+) -> impl Iterator<Item = (&'static str, FunctionMetadata)> {
+    let config = declared_config(code);
+
+    builtin_functions().filter(move |(_, x)| {
+        (x.target_ui == TargetUI::Any || config.ui.is_none_or(|ui| ui == x.target_ui))
+            && x.since_driver <= config.driver
+    })
+}
+
+/// Convert an offset in UTF-16 code units, as used by JavaScript strings, into a byte offset
+pub fn byte_offset(code: &str, utf16_offset: usize) -> usize {
+    let mut utf16_count = 0;
+
+    for (i, c) in code.char_indices() {
+        if utf16_count >= utf16_offset {
+            return i;
+        }
+        utf16_count += c.len_utf16();
+    }
+
+    code.len()
+}
+
+/// Convert a byte offset into an offset in UTF-16 code units
+pub fn utf16_offset(code: &str, byte_offset: usize) -> usize {
+    code[..scanner::char_boundary(code, byte_offset)]
+        .encode_utf16()
+        .count()
+}
+
+pub(crate) fn element_methods() -> impl Iterator<Item = FunctionMetadata> {
+    ElementMethods::iter().map(|x| get_element_method_metadata(&x))
+}
+
+/// Conversion methods callable on a value of type `ty`, or all of them when it's unknown
+pub(crate) fn conversion_methods(ty: Option<&str>) -> impl Iterator<Item = FunctionMetadata> + '_ {
+    ConversionMethods::iter()
+        .filter(move |x| ty.is_none_or(|ty| get_conversion_source_types(x).contains(&ty)))
+        .map(|x| get_conversion_method_metadata(&x))
+}
+
+pub(crate) fn builtin_enums() -> impl Iterator<Item = EnumMetadata> {
+    BuiltinPkgEnums::iter().map(|x| get_enum_metadata(&x))
+}
+
+/// Signature shown to the user, e.g. `NavigateToUrl(URL: string) -> void`
+pub(crate) fn signature_label(metadata: &FunctionMetadata) -> String {
+    format!(
+        "{}({}) -> {}",
+        metadata.name,
+        metadata
+            .args
+            .iter()
+            .map(|x| format!("{}: {}", x.display_name, x.arg_type))
+            .collect::<Vec<String>>()
+            .join(", "),
+        metadata.return_type
+    )
+}
+
+/// Markdown documentation of every argument of a function
+pub(crate) fn args_documentation(metadata: &FunctionMetadata) -> String {
+    metadata
+        .args
+        .iter()
+        .map(|x| {
+            let default_value = if x.default_value.is_empty() {
+                "".to_string()
+            } else {
+                format!(" Default: `{}`", x.default_value)
+            };
+
+            format!(
+                "- `{}` ({}): {}.{default_value}",
+                x.display_name, x.arg_type, x.description
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -0,0 +1,42 @@
+use serde::Serialize;
+
+use crate::checker::{self, diagnostic::Diagnostic};
+
+use super::SourceRange;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeAction {
+    pub title: String,
+    /// Range of the script to replace
+    pub range: SourceRange,
+    pub replacement: String,
+    /// Whether the action surely solves the diagnostic, rather than guessing what was meant
+    pub preferred: bool,
+    /// Diagnostic the action solves
+    pub diagnostic: Diagnostic,
+}
+
+/// Quick-fixes of the diagnostics touching the `range` of the script
+pub fn code_actions(code: &str, range: SourceRange) -> Vec<CodeAction> {
+    checker::check_program(code)
+        .into_iter()
+        .filter(|x| x.start <= range.end && range.start <= x.end)
+        .flat_map(|diagnostic| {
+            diagnostic
+                .fixes
+                .iter()
+                .map(|fix| CodeAction {
+                    title: fix.title.to_owned(),
+                    range: SourceRange {
+                        start: fix.start,
+                        end: fix.end,
+                    },
+                    replacement: fix.replacement.to_owned(),
+                    preferred: fix.preferred,
+                    diagnostic: diagnostic.clone(),
+                })
+                .collect::<Vec<CodeAction>>()
+        })
+        .collect()
+}
@@ -0,0 +1,131 @@
+use serde::Serialize;
+
+use crate::builtin_package_definition::{EnumMetadata, FunctionMetadata};
+
+use super::{
+    args_documentation, available_builtin_functions, builtin_enums, conversion_methods,
+    element_methods,
+    scanner::{self, WordContext},
+    signature_label, symbols,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CompletionKind {
+    Function,
+    Method,
+    Enum,
+    EnumMember,
+    Variable,
+    Keyword,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionItem {
+    pub label: String,
+    pub kind: CompletionKind,
+    pub detail: String,
+    /// Markdown documentation
+    pub documentation: String,
+}
+
+fn function_item(name: &str, metadata: &FunctionMetadata, kind: CompletionKind) -> CompletionItem {
+    CompletionItem {
+        label: name.to_string(),
+        kind,
+        detail: signature_label(metadata),
+        documentation: format!(
+            "{}\n\n{}",
+            metadata.description,
+            args_documentation(metadata)
+        ),
+    }
+}
+
+fn enum_member_items(enum_metadata: &EnumMetadata) -> Vec<CompletionItem> {
+    enum_metadata
+        .members
+        .iter()
+        .map(|x| CompletionItem {
+            label: x.name.to_string(),
+            kind: CompletionKind::EnumMember,
+            detail: format!("{}.{}", enum_metadata.name, x.name),
+            documentation: x.description.to_string(),
+        })
+        .collect()
+}
+
+/// Methods callable on a value of type `ty`, or on any value when it's unknown
+fn method_items(ty: Option<&str>) -> Vec<CompletionItem> {
+    let elements = match ty {
+        Some("element") | None => element_methods().collect(),
+        Some(_) => vec![],
+    };
+
+    elements
+        .into_iter()
+        .chain(conversion_methods(ty))
+        .map(|x| function_item(x.name, &x, CompletionKind::Method))
+        .collect()
+}
+
+/// Completion candidates for the cursor at `offset`
+pub fn complete(code: &str, offset: usize) -> Vec<CompletionItem> {
+    let offset = scanner::char_boundary(code, offset);
+
+    let state = scanner::scan(code, offset);
+    if state.in_string || state.in_comment {
+        return vec![];
+    }
+
+    let word_start = scanner::ident_start(code, offset);
+    let prefix = code[word_start..offset].to_lowercase();
+
+    let items = match scanner::word_context(code, word_start) {
+        WordContext::Builtin => available_builtin_functions(code)
+            .map(|(name, x)| function_item(name, &x, CompletionKind::Function))
+            .collect(),
+        WordContext::Member(receiver) => match builtin_enums().find(|x| x.name == receiver) {
+            Some(enum_metadata) => enum_member_items(&enum_metadata),
+            None => match symbols::lookup_variable(code, &receiver, word_start) {
+                Some(variable) => method_items(variable.var_type.as_deref()),
+                None => vec![],
+            },
+        },
+        WordContext::CallResultMember => method_items(None),
+        WordContext::LiteralMember(ty) => method_items(Some(ty)),
+        WordContext::Plain => {
+            let variables = symbols::collect_variables(code)
+                .into_iter()
+                .filter(|x| x.declaration.end < word_start)
+                .map(|x| CompletionItem {
+                    detail: x.var_type.clone().unwrap_or_else(|| "unknown".to_string()),
+                    label: x.name,
+                    kind: CompletionKind::Variable,
+                    documentation: "".to_string(),
+                });
+
+            let enums = builtin_enums().map(|x| CompletionItem {
+                label: x.name.to_string(),
+                kind: CompletionKind::Enum,
+                detail: x.type_name.to_string(),
+                documentation: x.description.to_string(),
+            });
+
+            let keywords = ["var", "const"].into_iter().map(|x| CompletionItem {
+                label: x.to_string(),
+                kind: CompletionKind::Keyword,
+                detail: "keyword".to_string(),
+                documentation: "".to_string(),
+            });
+
+            variables.chain(enums).chain(keywords).collect()
+        }
+    };
+
+    items
+        .into_iter()
+        .filter(|x| x.label.to_lowercase().starts_with(&prefix))
+        .collect()
+}
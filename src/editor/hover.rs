@@ -0,0 +1,92 @@
+use serde::Serialize;
+
+use crate::builtin_package_definition::FunctionMetadata;
+
+use super::{
+    args_documentation, builtin_enums, builtin_functions, conversion_methods, element_methods,
+    scanner::{self, WordContext},
+    signature_label, symbols, SourceRange,
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Hover {
+    /// Markdown shown to the user
+    pub contents: String,
+    pub range: SourceRange,
+}
+
+fn function_hover(prefix: &str, metadata: &FunctionMetadata) -> String {
+    let args = args_documentation(metadata);
+
+    format!(
+        "```\n{prefix}{}\n```\n\n{}{}",
+        signature_label(metadata),
+        metadata.description,
+        if args.is_empty() {
+            "".to_string()
+        } else {
+            format!("\n\n{args}")
+        }
+    )
+}
+
+fn method_hover(name: &str) -> Option<String> {
+    element_methods()
+        .find(|x| x.name == name)
+        .map(|x| function_hover("element.", &x))
+        .or_else(|| {
+            conversion_methods(None)
+                .find(|x| x.name == name)
+                .map(|x| function_hover("value.", &x))
+        })
+}
+
+/// Documentation of whatever is under the cursor at `offset`
+pub fn hover(code: &str, offset: usize) -> Option<Hover> {
+    let range = scanner::word_at(code, offset)?;
+    let word = &code[range.start..range.end];
+
+    let state = scanner::scan(code, range.start);
+    if state.in_string || state.in_comment {
+        return None;
+    }
+
+    let contents = match scanner::word_context(code, range.start) {
+        WordContext::Builtin => builtin_functions()
+            .find(|(name, _)| *name == word)
+            .map(|(_, x)| function_hover("#:", &x)),
+        WordContext::Member(receiver) => match builtin_enums().find(|x| x.name == receiver) {
+            Some(enum_metadata) => enum_metadata
+                .members
+                .iter()
+                .find(|x| x.name == word)
+                .map(|x| format!("```\n{receiver}.{}\n```\n\n{}", x.name, x.description)),
+            None => method_hover(word),
+        },
+        WordContext::CallResultMember | WordContext::LiteralMember(_) => method_hover(word),
+        WordContext::Plain => match builtin_enums().find(|x| x.name == word) {
+            Some(enum_metadata) => Some(format!(
+                "```\nenum {}\n```\n\n{}\n\n{}",
+                enum_metadata.name,
+                enum_metadata.description,
+                enum_metadata
+                    .members
+                    .iter()
+                    .map(|x| format!("- `{}`: {}", x.name, x.description))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            )),
+            None => symbols::lookup_variable(code, word, range.start).map(|x| {
+                format!(
+                    "```\n{} {}: {}\n```",
+                    if x.constant { "const" } else { "var" },
+                    x.name,
+                    x.var_type.as_deref().unwrap_or("unknown")
+                )
+            }),
+        },
+    }?;
+
+    Some(Hover { contents, range })
+}
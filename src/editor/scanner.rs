@@ -0,0 +1,246 @@
+//! Lexical helpers that keep working on scripts that are still being typed, and so don't parse yet.
+
+use super::SourceRange;
+
+/// Function being called, as written before its opening parenthesis
+#[derive(Debug, Clone, PartialEq)]
+pub enum Callee {
+    /// `#:Name(`
+    Builtin(String),
+    /// `value.name(`
+    Method(String),
+    Unknown,
+}
+
+#[derive(Debug, Clone)]
+pub struct OpenCall {
+    pub callee: Callee,
+    /// Index of the argument the cursor is on
+    pub active_arg: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct ScanState {
+    pub in_string: bool,
+    pub in_comment: bool,
+    /// Calls whose parentheses are still open, innermost last
+    pub open_calls: Vec<OpenCall>,
+}
+
+/// Word the cursor is on, and what comes before it
+#[derive(Debug, Clone, PartialEq)]
+pub enum WordContext {
+    /// Preceded by `#:`
+    Builtin,
+    /// Preceded by `Name.`
+    Member(String),
+    /// Preceded by `).`, a call result
+    CallResultMember,
+    /// Preceded by a literal and `.`, with the literal's type
+    LiteralMember(&'static str),
+    Plain,
+}
+
+pub fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// `offset` moved back to the start of the character it's in, and into the code
+pub fn char_boundary(code: &str, offset: usize) -> usize {
+    let mut offset = offset.min(code.len());
+    while !code.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    offset
+}
+
+/// Start of the identifier ending at `offset`
+pub fn ident_start(code: &str, offset: usize) -> usize {
+    let offset = char_boundary(code, offset);
+
+    code[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_ident_char(*c))
+        .last()
+        .map_or(offset, |(i, _)| i)
+}
+
+/// End of the identifier starting at `offset`
+pub fn ident_end(code: &str, offset: usize) -> usize {
+    let offset = char_boundary(code, offset);
+
+    code[offset..]
+        .char_indices()
+        .find(|(_, c)| !is_ident_char(*c))
+        .map_or(code.len(), |(i, _)| offset + i)
+}
+
+/// Range of the identifier the offset is on, if any
+pub fn word_at(code: &str, offset: usize) -> Option<SourceRange> {
+    let offset = char_boundary(code, offset);
+    let start = ident_start(code, offset);
+    let end = ident_end(code, offset);
+
+    (start < end).then_some(SourceRange { start, end })
+}
+
+/// What the identifier starting at `word_start` is attached to
+pub fn word_context(code: &str, word_start: usize) -> WordContext {
+    let before = &code[..word_start];
+
+    if before.ends_with("#:") {
+        return WordContext::Builtin;
+    }
+
+    match before.strip_suffix('.') {
+        Some(receiver) if receiver.ends_with(')') => WordContext::CallResultMember,
+        Some(receiver) if receiver.ends_with('"') => WordContext::LiteralMember("string"),
+        Some(receiver) => {
+            let receiver_start = ident_start(receiver, receiver.len());
+            let name = &receiver[receiver_start..];
+
+            if name == "true" || name == "false" {
+                WordContext::LiteralMember("bool")
+            } else if !name.is_empty() && name.chars().all(|x| x.is_ascii_digit()) {
+                // `1.5` is a decimal, not a member of `1`
+                match receiver[..receiver_start].ends_with('.') {
+                    true => WordContext::LiteralMember("decimal"),
+                    false => WordContext::LiteralMember("number"),
+                }
+            } else {
+                WordContext::Member(name.to_string())
+            }
+        }
+        None => WordContext::Plain,
+    }
+}
+
+fn callee_before(code: &str, paren: usize) -> Callee {
+    let name_start = ident_start(code, paren);
+    let name = code[name_start..paren].to_string();
+
+    if name.is_empty() {
+        return Callee::Unknown;
+    }
+
+    match word_context(code, name_start) {
+        WordContext::Builtin => Callee::Builtin(name),
+        WordContext::Member(_) | WordContext::CallResultMember | WordContext::LiteralMember(_) => {
+            Callee::Method(name)
+        }
+        WordContext::Plain => Callee::Unknown,
+    }
+}
+
+/// Scan the code up to `offset`, keeping track of strings, comments and open calls
+pub fn scan(code: &str, offset: usize) -> ScanState {
+    let code_until = &code[..char_boundary(code, offset)];
+    let mut state = ScanState::default();
+    let mut chars = code_until.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if state.in_string {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => state.in_string = false,
+                _ => (),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => state.in_string = true,
+            '/' if chars.peek().map(|x| x.1) == Some('/') => {
+                // Skip until the end of line
+                state.in_comment = true;
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        state.in_comment = false;
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek().map(|x| x.1) == Some('*') => {
+                chars.next();
+                state.in_comment = true;
+                while let Some((_, c)) = chars.next() {
+                    if c == '*' && chars.peek().map(|x| x.1) == Some('/') {
+                        chars.next();
+                        state.in_comment = false;
+                        break;
+                    }
+                }
+            }
+            '(' => state.open_calls.push(OpenCall {
+                callee: callee_before(code_until, i),
+                active_arg: 0,
+            }),
+            ')' => {
+                state.open_calls.pop();
+            }
+            ',' => {
+                if let Some(call) = state.open_calls.last_mut() {
+                    call.active_arg += 1;
+                }
+            }
+            // A statement end means any unclosed call was a typo
+            ';' => state.open_calls.clear(),
+            _ => (),
+        }
+    }
+
+    state
+}
+
+/// Split the code into `;` terminated statements, ignoring separators inside strings and comments.
+/// Returns the byte offset of each statement alongside its text.
+pub fn split_statements(code: &str) -> Vec<(usize, &str)> {
+    let mut statements = vec![];
+    let mut statement_start = 0;
+    let mut in_string = false;
+    let mut chars = code.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if in_string {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => in_string = false,
+                _ => (),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '/' if chars.peek().map(|x| x.1) == Some('/') => {
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek().map(|x| x.1) == Some('*') => {
+                chars.next();
+                while let Some((_, c)) = chars.next() {
+                    if c == '*' && chars.peek().map(|x| x.1) == Some('/') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            ';' => {
+                statements.push((statement_start, &code[statement_start..=i]));
+                statement_start = i + 1;
+            }
+            _ => (),
+        }
+    }
+
+    statements
+}
@@ -0,0 +1,59 @@
+use serde::Serialize;
+
+use super::{
+    builtin_functions, conversion_methods, element_methods,
+    scanner::{self, Callee},
+    signature_label,
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParameterInformation {
+    pub label: String,
+    pub documentation: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureHelp {
+    pub label: String,
+    pub documentation: String,
+    pub parameters: Vec<ParameterInformation>,
+    pub active_parameter: usize,
+}
+
+/// Signature of the innermost call the cursor at `offset` is in
+pub fn signature_help(code: &str, offset: usize) -> Option<SignatureHelp> {
+    let state = scanner::scan(code, offset);
+    if state.in_comment {
+        return None;
+    }
+
+    let call = state.open_calls.last()?;
+
+    let metadata = match &call.callee {
+        Callee::Builtin(name) => builtin_functions().find(|x| x.0 == name)?.1,
+        Callee::Method(name) => element_methods()
+            .chain(conversion_methods(None))
+            .find(|x| x.name == name)?,
+        Callee::Unknown => return None,
+    };
+
+    Some(SignatureHelp {
+        label: signature_label(&metadata),
+        documentation: metadata.description.to_string(),
+        parameters: metadata
+            .args
+            .iter()
+            .map(|x| ParameterInformation {
+                label: format!("{}: {}", x.display_name, x.arg_type),
+                documentation: if x.default_value.is_empty() {
+                    x.description.to_string()
+                } else {
+                    format!("{}. Default: `{}`", x.description, x.default_value)
+                },
+            })
+            .collect(),
+        active_parameter: call.active_arg,
+    })
+}
@@ -0,0 +1,157 @@
+use pest::Parser;
+use serde::Serialize;
+
+use crate::{
+    ast::{self, CfgEntry, Statement, StatementKind},
+    autalonparser::{AutalonParser, Rule},
+    checker::{scope::Variable, Checker},
+    trivia::Trivia,
+};
+
+use super::{
+    scanner::{self, WordContext},
+    SourceRange,
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariableSymbol {
+    pub name: String,
+    /// Type of the variable, if the declared expression type checks
+    pub var_type: Option<String>,
+    /// Declared with `const`
+    pub constant: bool,
+    /// Range of the variable name in its declaration
+    pub declaration: SourceRange,
+}
+
+/// Length of the whitespace, comments and cfg sections before a statement
+fn leading_trivia_len(text: &str) -> usize {
+    let mut rest = text;
+
+    loop {
+        let trimmed = rest.trim_start();
+
+        let skipped = if trimmed.starts_with("//") {
+            trimmed.find('\n').map_or(trimmed.len(), |x| x + 1)
+        } else if trimmed.starts_with("/*") {
+            trimmed.find("*/").map_or(trimmed.len(), |x| x + 2)
+        } else if trimmed.starts_with("#[") {
+            trimmed.find(']').map_or(trimmed.len(), |x| x + 1)
+        } else {
+            return text.len() - trimmed.len();
+        };
+
+        rest = &trimmed[skipped..];
+    }
+}
+
+/// Entries of the cfg sections at the top of the script. Their spans are within their section.
+pub fn cfg_entries(code: &str) -> Vec<CfgEntry> {
+    let mut entries = vec![];
+    let mut rest = code;
+
+    loop {
+        let trimmed = rest.trim_start();
+
+        let skipped = if trimmed.starts_with("//") {
+            trimmed.find('\n').map_or(trimmed.len(), |x| x + 1)
+        } else if trimmed.starts_with("/*") {
+            trimmed.find("*/").map_or(trimmed.len(), |x| x + 2)
+        } else if trimmed.starts_with("#[") {
+            let end = trimmed.find(']').map_or(trimmed.len(), |x| x + 1);
+
+            let entry = AutalonParser::parse(Rule::cfg_section, &trimmed[..end])
+                .ok()
+                .and_then(|mut x| x.next())
+                .and_then(|x| ast::build_cfg(x).ok());
+            entries.extend(entry);
+
+            end
+        } else {
+            return entries;
+        };
+
+        rest = &trimmed[skipped..];
+    }
+}
+
+/// Statements of the script that parse on their own, with the offset they start at
+pub fn parse_statements(code: &str) -> Vec<(usize, Statement)> {
+    scanner::split_statements(code)
+        .into_iter()
+        .filter_map(|(offset, text)| {
+            let start = offset + leading_trivia_len(text);
+            let statement_code = &code[start..offset + text.len()];
+
+            let pair = AutalonParser::parse(Rule::statement, statement_code)
+                .ok()?
+                .next()?;
+            let statement = ast::build_statement(pair, Trivia::default()).ok()?;
+
+            Some((start, statement))
+        })
+        .collect()
+}
+
+/// Every variable declared in the script. Statements that don't parse are skipped,
+/// so this keeps working while the script is being edited.
+pub fn collect_variables(code: &str) -> Vec<VariableSymbol> {
+    let mut checker = Checker::new();
+    let mut variables = vec![];
+
+    for (start, statement) in parse_statements(code) {
+        let StatementKind::VarDeclaration {
+            name: identifier,
+            value: mut expr,
+            constant,
+        } = statement.kind
+        else {
+            continue;
+        };
+
+        let var_type = checker.get_expr_returntype(&mut expr).ok();
+        if let Some(var_type) = var_type {
+            // Later declarations may refer to this one
+            let _ = checker.var_insert(
+                &identifier,
+                Variable {
+                    var_type,
+                    declaration: identifier.span,
+                    value: None,
+                },
+            );
+        }
+
+        variables.push(VariableSymbol {
+            var_type: var_type.map(str::to_string),
+            constant,
+            declaration: SourceRange {
+                start: start + identifier.span.start,
+                end: start + identifier.span.end,
+            },
+            name: identifier.name,
+        });
+    }
+
+    variables
+}
+
+/// Variable declared most recently before `offset` with the given name
+pub fn lookup_variable(code: &str, name: &str, offset: usize) -> Option<VariableSymbol> {
+    collect_variables(code)
+        .into_iter()
+        .rev()
+        .find(|x| x.name == name && x.declaration.start <= offset)
+}
+
+/// Where the variable under the cursor was declared
+pub fn definition(code: &str, offset: usize) -> Option<SourceRange> {
+    let word = scanner::word_at(code, offset)?;
+
+    if scanner::word_context(code, word.start) != WordContext::Plain {
+        return None;
+    }
+
+    lookup_variable(code, &code[word.start..word.end], word.start).map(|x| x.declaration)
+}
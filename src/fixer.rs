@@ -0,0 +1,103 @@
+//! Migration of scripts written for older versions of the transpiler.
+//!
+//! Applies the preferred fixes the checker offers: deprecated builtins are renamed, numbers given
+//! as strings lose their quotes, missing default arguments are inserted, and arguments are
+//! reordered when each of them fits a single parameter.
+//! Finally `#[version=]` is set to the current version. Fixes that overlap are applied over
+//! several passes.
+
+use color_eyre::eyre::Report;
+use serde::Serialize;
+
+use crate::{
+    ast,
+    checker::{self, diagnostic::Fix},
+};
+
+/// Passes after which a script that keeps getting fixes is left as is
+const MAX_PASSES: usize = 10;
+
+/// Version written into `#[version=]`
+pub const SCRIPT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixedScript {
+    pub code: String,
+    /// Unified diff from the original script, empty when nothing changed
+    pub diff: String,
+    /// Titles of the applied fixes, in order
+    pub applied: Vec<String>,
+}
+
+/// Fix a script. `name` is the file name shown in the diff header.
+#[tracing::instrument(skip(code))]
+pub fn fix_program(code: &str, name: &str) -> Result<FixedScript, Report> {
+    // Scripts that don't parse have nothing to fix yet
+    ast::parse_program(code)?;
+
+    let mut fixed = code.to_string();
+    let mut applied = vec![];
+
+    for _ in 0..MAX_PASSES {
+        // Guesses are left to the editor quick-fixes
+        let fixes = checker::check_program(&fixed)
+            .into_iter()
+            .filter_map(|x| x.fixes.into_iter().find(|x| x.preferred))
+            .filter(|x| fixed.get(x.start..x.end) != Some(x.replacement.as_str()))
+            .collect::<Vec<Fix>>();
+        if fixes.is_empty() {
+            break;
+        }
+
+        applied.extend(apply_fixes(&mut fixed, fixes));
+    }
+
+    if let Some(title) = update_version(&mut fixed)? {
+        applied.push(title);
+    }
+
+    let diff = similar::TextDiff::from_lines(code, &fixed)
+        .unified_diff()
+        .header(&format!("a/{name}"), &format!("b/{name}"))
+        .to_string();
+
+    Ok(FixedScript {
+        code: fixed,
+        diff,
+        applied,
+    })
+}
+
+/// Apply the fixes that don't overlap an earlier one, returning their titles
+fn apply_fixes(code: &mut String, mut fixes: Vec<Fix>) -> Vec<String> {
+    fixes.sort_by_key(|x| (x.start, x.end));
+
+    let mut kept: Vec<Fix> = vec![];
+    for fix in fixes {
+        if kept.last().is_none_or(|last| last.end <= fix.start) {
+            kept.push(fix);
+        }
+    }
+
+    // From the end, so the offsets of the other fixes stay valid
+    for fix in kept.iter().rev() {
+        code.replace_range(fix.start..fix.end, &fix.replacement);
+    }
+
+    kept.into_iter().map(|x| x.title).collect()
+}
+
+/// Set `#[version=]` to the current version, adding it when missing
+fn update_version(code: &mut String) -> Result<Option<String>, Report> {
+    let program = ast::parse_program(code)?;
+    let section = format!("#[version={SCRIPT_VERSION}]");
+
+    match program.cfg.iter().find(|x| x.key == "version") {
+        Some(entry) if entry.value == SCRIPT_VERSION => return Ok(None),
+        Some(entry) => code.replace_range(entry.span.start..entry.span.end, &section),
+        None => code.insert_str(0, &format!("{section}\n")),
+    }
+
+    Ok(Some(format!("Set the version to {SCRIPT_VERSION}")))
+}
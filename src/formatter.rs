@@ -0,0 +1,260 @@
+//! Canonical re-printing of Autalon scripts.
+//!
+//! Works on the syntax tree from [`crate::ast`]. Comments inside a statement are moved above it.
+
+use crate::{
+    ast::{self, Assoc, Expr, ExprKind, Literal, StatementKind, StringPart, AUTALON_OPERATORS},
+    trivia::Comment,
+};
+use color_eyre::eyre::Report;
+
+/// Statements longer than this get their arguments wrapped, one per line
+const MAX_WIDTH: usize = 100;
+const INDENT: &str = "    ";
+
+/// Something printed on its own line(s)
+struct Item {
+    text: String,
+    is_comment: bool,
+    /// Comments moved out of a statement never trail the previous line
+    is_moved: bool,
+    start: usize,
+    end: usize,
+}
+
+/// Format a script. The output is stable, formatting it again doesn't change it.
+#[tracing::instrument(skip_all)]
+pub fn format_program(code: &str) -> Result<String, Report> {
+    let program = ast::parse_program(code)?;
+    let mut items: Vec<Item> = vec![];
+
+    for entry in &program.cfg {
+        items.push(Item {
+            text: format!("#[{}={}]", entry.key, entry.value),
+            is_comment: false,
+            is_moved: false,
+            start: entry.span.start,
+            end: entry.span.end,
+        });
+    }
+
+    for statement in &program.statements {
+        let span = statement.span;
+
+        // Comments inside the statement are moved above it, the others keep their place
+        let (inner, outer): (Vec<Comment>, Vec<Comment>) = statement
+            .trivia
+            .leading
+            .iter()
+            .cloned()
+            .partition(|x| x.start >= span.start && x.end <= span.end);
+
+        items.extend(comment_items(outer, None));
+        items.extend(comment_items(inner, Some(span.start)));
+        items.push(Item {
+            text: statement_format(&statement.kind),
+            is_comment: false,
+            is_moved: false,
+            start: span.start,
+            end: span.end,
+        });
+        items.extend(comment_items(statement.trivia.trailing.clone(), None));
+    }
+    items.extend(comment_items(program.dangling, None));
+
+    // Comments before the cfg sections are attached to the first statement
+    items.sort_by_key(|x| x.start);
+
+    Ok(print_items(code, &items))
+}
+
+fn print_items(code: &str, items: &[Item]) -> String {
+    let mut res = String::new();
+    let mut previous: Option<&Item> = None;
+
+    for item in items {
+        if let Some(previous) = previous {
+            let gap = &code[previous.end.min(item.start)..item.start];
+            let newlines = gap.matches('\n').count();
+
+            if item.is_comment && !item.is_moved && !previous.is_comment && newlines == 0 {
+                // Trailing comment, kept on the line it annotates
+                res.push(' ');
+            } else if newlines > 1 || is_cfg_end(code, previous, item) {
+                res.push_str("\n\n");
+            } else {
+                res.push('\n');
+            }
+        }
+
+        res.push_str(&item.text);
+        previous = Some(item);
+    }
+
+    if !res.is_empty() {
+        res.push('\n');
+    }
+
+    res
+}
+
+/// cfg sections are separated from what follows them by a blank line
+fn is_cfg_end(code: &str, previous: &Item, item: &Item) -> bool {
+    code[previous.start..].starts_with("#[") && !code[item.start..].starts_with("#[")
+}
+
+fn comment_items(comments: Vec<Comment>, moved_to: Option<usize>) -> impl Iterator<Item = Item> {
+    comments.into_iter().map(move |x| Item {
+        text: x.source(),
+        is_comment: true,
+        is_moved: moved_to.is_some(),
+        // Moved comments are printed on their own line, above the statement
+        start: moved_to.unwrap_or(x.start),
+        end: moved_to.unwrap_or(x.end),
+    })
+}
+
+fn statement_format(kind: &StatementKind) -> String {
+    let format = |wrap| match kind {
+        StatementKind::VarDeclaration {
+            name,
+            value,
+            constant,
+        } => {
+            let keyword = if *constant { "const" } else { "var" };
+            format!("{keyword} {} = {}", name.name, expr_format(value, wrap))
+        }
+        StatementKind::VarAssignment { name, value } => {
+            format!("{} = {}", name.name, expr_format(value, wrap))
+        }
+        StatementKind::Expr(expr) => expr_format(expr, wrap),
+        StatementKind::Escape(content) => format!("unparsed ###{{{content}}}###"),
+    } + ";";
+
+    let flat = format(false);
+    if flat.len() <= MAX_WIDTH {
+        return flat;
+    }
+
+    format(true)
+}
+
+/// Format an expression on a single line
+pub fn format_expr(expr: &Expr) -> String {
+    expr_format(expr, false)
+}
+
+/// Format an expression. With `wrap`, the arguments of its outermost calls are put one per line.
+fn expr_format(expr: &Expr, wrap: bool) -> String {
+    match &expr.kind {
+        ExprKind::Literal(Literal::String(value)) => format!("\"{value}\""),
+        ExprKind::Literal(Literal::Number(value)) => value.clone(),
+        // Digits are kept as written, trailing zeros can matter
+        ExprKind::Literal(Literal::Decimal(value)) => value.replace('E', "e"),
+        ExprKind::Literal(Literal::Bool(value)) => value.to_string(),
+        ExprKind::Interpolation(parts) => format!(
+            "\"{}\"",
+            parts
+                .iter()
+                .map(|part| match part {
+                    StringPart::Text(text) => text.clone(),
+                    StringPart::Expr(expr) => format!("${{{}}}", expr_format(expr, false)),
+                })
+                .collect::<String>()
+        ),
+
+        ExprKind::Variable(identifier) => identifier.name.clone(),
+        ExprKind::EnumMember {
+            package,
+            name,
+            member,
+        } => format!("{}{}.{}", package_format(package), name.name, member.name),
+
+        ExprKind::Call {
+            package,
+            name,
+            args,
+            ..
+        } => format!(
+            "{}{}{}",
+            package_format(package),
+            name.name,
+            args_format(args, wrap)
+        ),
+        ExprKind::MethodCall {
+            receiver,
+            name,
+            args,
+        } => {
+            let receiver_text = expr_format(receiver, wrap);
+            let receiver_text = match receiver.is_receiver() {
+                true => receiver_text,
+                false => format!("({receiver_text})"),
+            };
+
+            format!("{receiver_text}.{}{}", name.name, args_format(args, false))
+        }
+
+        ExprKind::Unary { op, operand } => {
+            format!(
+                "{op}{}",
+                operand_format(&expr.kind, operand, Assoc::Right, false)
+            )
+        }
+        // Only arithmetic can hold calls whose arguments are worth wrapping
+        ExprKind::Binary { op, lhs, rhs } => {
+            let wrap = wrap && op.is_arithmetic();
+            format!(
+                "{} {op} {}",
+                operand_format(&expr.kind, lhs, Assoc::Left, wrap),
+                operand_format(&expr.kind, rhs, Assoc::Right, wrap)
+            )
+        }
+        ExprKind::Index { target, index } => format!(
+            "{}[{}]",
+            expr_format(target, false),
+            expr_format(index, false)
+        ),
+    }
+}
+
+/// Format an operand, keeping only the parentheses the grouping needs
+fn operand_format(parent: &ExprKind, operand: &Expr, side: Assoc, wrap: bool) -> String {
+    let formatted = expr_format(operand, wrap);
+
+    if AUTALON_OPERATORS.needs_parens(parent, operand, side) {
+        return format!("({formatted})");
+    }
+
+    formatted
+}
+
+fn package_format(package: &Option<String>) -> String {
+    package.as_ref().map_or(String::new(), |x| format!("{x}:"))
+}
+
+fn args_format(args: &[Expr], wrap: bool) -> String {
+    let args = args
+        .iter()
+        .map(|x| expr_format(x, false))
+        .collect::<Vec<String>>();
+
+    if wrap && !args.is_empty() {
+        format!(
+            "(\n{}\n)",
+            args.iter()
+                .map(|x| indent(x))
+                .collect::<Vec<String>>()
+                .join(",\n")
+        )
+    } else {
+        format!("({})", args.join(", "))
+    }
+}
+
+fn indent(text: &str) -> String {
+    text.lines()
+        .map(|x| format!("{INDENT}{x}"))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -1,8 +1,13 @@
+pub mod ast;
 pub mod autalonparser;
 pub mod builtin_package_definition;
 pub mod checker;
+pub mod editor;
+pub mod fixer;
+pub mod formatter;
 pub mod libs;
 pub mod transpiler;
+pub mod trivia;
 
 #[cfg(test)]
 mod tests;
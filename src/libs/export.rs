@@ -1,31 +1,58 @@
 use eyre::Context;
 
-use crate::{autalonparser, builtin_package_definition, checker, transpiler};
+use crate::{ast, builtin_package_definition, checker, editor, fixer, formatter, transpiler};
+
+use super::katalon_project;
 
 pub fn transpile_groovy(code: &str) -> Result<String, color_eyre::Report> {
-    use autalonparser::{AutalonParser, Rule};
-    use pest::{iterators::Pair, Parser};
+    transpile(
+        code,
+        transpiler::TranspilerOption::Groovy,
+        &transpiler::TranspileOptions::default(),
+    )
+    .map(|x| x.code)
+}
 
-    let parsed = AutalonParser::parse(Rule::program, code).expect("Failed to parse");
+pub fn transpile(
+    code: &str,
+    target: transpiler::TranspilerOption,
+    options: &transpiler::TranspileOptions,
+) -> Result<transpiler::source_map::Transpiled, color_eyre::Report> {
+    let mut program = ast::parse_program(code)?;
 
-    let mut checked_pair: Vec<Pair<Rule>> = vec![];
+    if let Err(err) = checker::check_parsed_program(&mut program) {
+        eyre::bail!(format!("{:?}", err))
+    }
 
-    for pair in parsed {
-        if pair.as_rule() == Rule::cfg_section {
-            continue; // Skip cfg for now
-        }
+    transpiler::program_handler(target, &program, options)
+}
 
-        if pair.as_rule() == Rule::EOI {
-            break; // End of Input
-        }
+pub fn check(code: &str) -> Vec<checker::diagnostic::Diagnostic> {
+    checker::check_program(code)
+}
 
-        match checker::statement_checker(pair.clone()) {
-            Ok(_) => checked_pair.push(pair),
-            Err(err) => eyre::bail!(format!("{:?}", err)),
-        }
-    }
+/// Re-print a script canonically
+pub fn format(code: &str) -> Result<String, color_eyre::Report> {
+    formatter::format_program(code)
+}
 
-    transpiler::program_handler(transpiler::TranspilerOption::Groovy, &checked_pair)
+/// Migrate a script to the current version. `name` is the file name shown in the diff.
+pub fn fix(code: &str, name: &str) -> Result<fixer::FixedScript, color_eyre::Report> {
+    fixer::fix_program(code, name)
+}
+
+/// Package `(test case name, autalon script)` pairs into a zipped Katalon project
+pub fn package_katalon(scripts: &[(String, String)]) -> Result<Vec<u8>, color_eyre::Report> {
+    let transpiled = scripts
+        .iter()
+        .map(|(name, code)| {
+            transpile_groovy(code)
+                .map(|script| (name.to_owned(), script))
+                .with_context(|| format!("Failed to transpile '{name}'"))
+        })
+        .collect::<Result<Vec<(String, String)>, color_eyre::Report>>()?;
+
+    katalon_project::package(&transpiled)
 }
 
 pub fn get_fn_metadata() -> Result<String, color_eyre::Report> {
@@ -38,3 +65,58 @@ pub fn get_fn_metadata() -> Result<String, color_eyre::Report> {
 
     serde_json::to_string_pretty(&metadata_list).context("Failed to format metadata list")
 }
+
+pub fn get_enum_metadata() -> Result<String, color_eyre::Report> {
+    use builtin_package_definition::{get_enum_metadata, BuiltinPkgEnums, EnumMetadata};
+    use strum::IntoEnumIterator;
+
+    let metadata_list: Vec<EnumMetadata> = BuiltinPkgEnums::iter()
+        .map(|x| get_enum_metadata(&x))
+        .collect();
+
+    serde_json::to_string_pretty(&metadata_list).context("Failed to format metadata list")
+}
+
+/// Deprecated builtin names with their replacements as JSON
+pub fn get_alias_metadata() -> Result<String, color_eyre::Report> {
+    use builtin_package_definition::{get_alias_metadata, AliasMetadata, BuiltinPkgAliases};
+    use strum::IntoEnumIterator;
+
+    let metadata_list: Vec<AliasMetadata> = BuiltinPkgAliases::iter()
+        .map(|x| get_alias_metadata(&x))
+        .collect();
+
+    serde_json::to_string_pretty(&metadata_list).context("Failed to format metadata list")
+}
+
+/// Completion candidates at the byte `offset` as JSON
+pub fn complete(code: &str, offset: usize) -> Result<String, color_eyre::Report> {
+    serde_json::to_string_pretty(&editor::complete(code, offset))
+        .context("Failed to format completion list")
+}
+
+/// Hover documentation at the byte `offset` as JSON, or `null` when there's nothing to show
+pub fn hover(code: &str, offset: usize) -> Result<String, color_eyre::Report> {
+    serde_json::to_string_pretty(&editor::hover(code, offset)).context("Failed to format hover")
+}
+
+/// Signature of the call the byte `offset` is in as JSON, or `null` when outside of any call
+pub fn signature_help(code: &str, offset: usize) -> Result<String, color_eyre::Report> {
+    serde_json::to_string_pretty(&editor::signature_help(code, offset))
+        .context("Failed to format signature help")
+}
+
+/// Quick-fixes of the diagnostics touching the `start` to `end` byte range as JSON
+pub fn code_actions(code: &str, start: usize, end: usize) -> Result<String, color_eyre::Report> {
+    serde_json::to_string_pretty(&editor::code_actions(
+        code,
+        editor::SourceRange { start, end },
+    ))
+    .context("Failed to format code actions")
+}
+
+/// Parsed and checked program as JSON, see [`ast::json`] for the schema
+pub fn parse_to_json(code: &str) -> Result<String, color_eyre::Report> {
+    serde_json::to_string_pretty(&ast::json::parse_to_json(code)?)
+        .context("Failed to format program")
+}
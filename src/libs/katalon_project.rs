@@ -0,0 +1,75 @@
+use std::io::{Cursor, Write};
+
+use eyre::Context;
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+/// Stable hash of the test case name, so re-packaging the same script keeps its Katalon ids
+fn name_hash(name: &str) -> u64 {
+    // FNV-1a
+    name.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Path of the test case script inside a Katalon project, e.g. `Scripts/Login/Script1665012345678.groovy`
+pub fn script_path(test_case_name: &str) -> String {
+    // Katalon names scripts after the creation time in millis, which is always 13 digits long
+    let script_id = 1_000_000_000_000 + name_hash(test_case_name) % 9_000_000_000_000;
+
+    format!("Scripts/{test_case_name}/Script{script_id}.groovy")
+}
+
+/// Path of the test case entity inside a Katalon project, e.g. `Test Cases/Login.tc`
+pub fn test_case_path(test_case_name: &str) -> String {
+    format!("Test Cases/{test_case_name}.tc")
+}
+
+pub fn test_case_entity(test_case_name: &str) -> String {
+    let hash = format!(
+        "{:016x}{:016x}",
+        name_hash(test_case_name),
+        name_hash(&test_case_name.chars().rev().collect::<String>())
+    );
+    let guid = format!(
+        "{}-{}-{}-{}-{}",
+        &hash[0..8],
+        &hash[8..12],
+        &hash[12..16],
+        &hash[16..20],
+        &hash[20..32]
+    );
+
+    // Test cases can be nested in folders, but the entity only holds the name itself
+    let name = test_case_name.rsplit('/').next().unwrap_or(test_case_name);
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<TestCaseEntity>
+   <description>Generated by Autalon Transpiler</description>
+   <name>{name}</name>
+   <tag></tag>
+   <comment></comment>
+   <testCaseGuid>{guid}</testCaseGuid>
+</TestCaseEntity>
+"#
+    )
+}
+
+/// Zip transpiled scripts into Katalon's project layout. Takes a list of `(test case name, groovy script)`.
+pub fn package(scripts: &[(String, String)]) -> Result<Vec<u8>, color_eyre::Report> {
+    let mut zip = ZipWriter::new(Cursor::new(vec![]));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for (test_case_name, script) in scripts {
+        zip.start_file(test_case_path(test_case_name), options)?;
+        zip.write_all(test_case_entity(test_case_name).as_bytes())?;
+
+        zip.start_file(script_path(test_case_name), options)?;
+        zip.write_all(script.as_bytes())?;
+    }
+
+    Ok(zip
+        .finish()
+        .context("Failed to write Katalon package")?
+        .into_inner())
+}
@@ -1,3 +1,6 @@
 pub mod export;
 pub mod init;
+pub mod katalon_project;
 pub mod wasm_export;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod watch;
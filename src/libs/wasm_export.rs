@@ -1,6 +1,6 @@
 use wasm_bindgen::prelude::*;
 
-use crate::{autalonparser, builtin_package_definition, checker, transpiler};
+use crate::{ast, builtin_package_definition, checker, editor, fixer, formatter, transpiler};
 
 use super::init;
 
@@ -11,39 +11,84 @@ pub fn startup() -> Result<(), JsValue> {
     Ok(())
 }
 
+fn transpile_program(
+    code: &str,
+    options: &transpiler::TranspileOptions,
+) -> Result<transpiler::source_map::Transpiled, String> {
+    let mut program = ast::parse_program(code).map_err(|err| format!("{:?}", err))?;
+
+    if let Err(err) = checker::check_parsed_program(&mut program) {
+        return Err(format!("{:?}", err));
+    }
+
+    transpiler::program_handler(transpiler::TranspilerOption::Groovy, &program, options).map_err(
+        |err| {
+            tracing::error!(err = err.to_string(), "Failed transpiling script!");
+            format!("{:?}", err)
+        },
+    )
+}
+
 #[wasm_bindgen]
 pub fn transpile_groovy(code: &str) -> Result<String, String> {
-    use autalonparser::{AutalonParser, Rule};
-    use pest::{iterators::Pair, Parser};
+    transpile_program(code, &transpiler::TranspileOptions::default()).map(|x| x.code)
+}
 
-    let parsed = AutalonParser::parse(Rule::program, code).expect("Failed to parse");
+/// Transpile into Groovy, returning `{ code, sourceMap }` as JSON
+#[wasm_bindgen]
+pub fn transpile_groovy_with_source_map(
+    code: &str,
+    file_name: Option<String>,
+    line_markers: bool,
+) -> Result<String, String> {
+    let transpiled = transpile_program(
+        code,
+        &transpiler::TranspileOptions {
+            file_name,
+            line_markers,
+        },
+    )?;
 
-    let mut checked_pair: Vec<Pair<Rule>> = vec![];
+    match serde_json::to_string_pretty(&transpiled) {
+        Ok(transpiled) => Ok(transpiled),
+        Err(err) => Err(format!("{:?}", err)),
+    }
+}
 
-    for pair in parsed {
-        if pair.as_rule() == Rule::cfg_section {
-            continue; // Skip cfg for now
-        }
+/// Rewrite the Groovy locations of a stack trace into script locations, using a source map
+/// returned by `transpile_groovy_with_source_map`
+#[wasm_bindgen]
+pub fn translate_stack_trace(
+    source_map: &str,
+    trace: &str,
+    generated_file: Option<String>,
+) -> Result<String, String> {
+    let source_map: transpiler::source_map::SourceMap =
+        serde_json::from_str(source_map).map_err(|err| format!("{:?}", err))?;
 
-        if pair.as_rule() == Rule::EOI {
-            break; // End of Input
-        }
+    Ok(source_map.translate_stack_trace(trace, generated_file.as_deref()))
+}
 
-        match checker::statement_checker(pair.clone()) {
-            Ok(_) => checked_pair.push(pair),
-            Err(err) => return Err(format!("{:?}", err)),
-        }
-    }
+/// Re-print a script canonically
+#[wasm_bindgen]
+pub fn format(code: &str) -> Result<String, String> {
+    formatter::format_program(code).map_err(|err| format!("{:?}", err))
+}
 
-    Ok(
-        match transpiler::program_handler(transpiler::TranspilerOption::Groovy, &checked_pair) {
-            Ok(res) => res,
-            Err(err) => {
-                tracing::error!(err = err.to_string(), "Failed transpiling script!");
-                "".to_string()
-            }
-        },
-    )
+/// Script migrated to the current version as JSON, with the unified diff and the applied fixes
+#[wasm_bindgen]
+pub fn fix(code: &str, name: &str) -> Result<String, String> {
+    let fixed = fixer::fix_program(code, name).map_err(|err| format!("{:?}", err))?;
+
+    serde_json::to_string_pretty(&fixed).map_err(|err| format!("{:?}", err))
+}
+
+/// Parsed and checked program as JSON, see `ast::json` for the schema
+#[wasm_bindgen]
+pub fn parse_to_json(code: &str) -> Result<String, String> {
+    let program = ast::json::parse_to_json(code).map_err(|err| format!("{:?}", err))?;
+
+    serde_json::to_string_pretty(&program).map_err(|err| format!("{:?}", err))
 }
 
 #[wasm_bindgen]
@@ -60,3 +105,98 @@ pub fn get_fn_metadata() -> Result<String, String> {
         Err(err) => Err(format!("{:?}", err)),
     }
 }
+
+#[wasm_bindgen]
+pub fn get_enum_metadata() -> Result<String, String> {
+    use builtin_package_definition::{get_enum_metadata, BuiltinPkgEnums, EnumMetadata};
+    use strum::IntoEnumIterator;
+
+    let metadata_list: Vec<EnumMetadata> = BuiltinPkgEnums::iter()
+        .map(|x| get_enum_metadata(&x))
+        .collect();
+
+    match serde_json::to_string_pretty(&metadata_list) {
+        Ok(list) => Ok(list),
+        Err(err) => Err(format!("{:?}", err)),
+    }
+}
+
+/// Deprecated builtin names with their replacements as JSON
+#[wasm_bindgen]
+pub fn get_alias_metadata() -> Result<String, String> {
+    use builtin_package_definition::{get_alias_metadata, AliasMetadata, BuiltinPkgAliases};
+    use strum::IntoEnumIterator;
+
+    let metadata_list: Vec<AliasMetadata> = BuiltinPkgAliases::iter()
+        .map(|x| get_alias_metadata(&x))
+        .collect();
+
+    match serde_json::to_string_pretty(&metadata_list) {
+        Ok(list) => Ok(list),
+        Err(err) => Err(format!("{:?}", err)),
+    }
+}
+
+/// Completion candidates as JSON. Offsets count UTF-16 code units, like JavaScript strings.
+#[wasm_bindgen]
+pub fn complete(code: &str, offset: usize) -> Result<String, String> {
+    let items = editor::complete(code, editor::byte_offset(code, offset));
+
+    match serde_json::to_string_pretty(&items) {
+        Ok(list) => Ok(list),
+        Err(err) => Err(format!("{:?}", err)),
+    }
+}
+
+/// Hover documentation as JSON, or `null` when there's nothing to show
+#[wasm_bindgen]
+pub fn hover(code: &str, offset: usize) -> Result<String, String> {
+    let hover = editor::hover(code, editor::byte_offset(code, offset)).map(|mut hover| {
+        hover.range = editor::SourceRange {
+            start: editor::utf16_offset(code, hover.range.start),
+            end: editor::utf16_offset(code, hover.range.end),
+        };
+        hover
+    });
+
+    match serde_json::to_string_pretty(&hover) {
+        Ok(hover) => Ok(hover),
+        Err(err) => Err(format!("{:?}", err)),
+    }
+}
+
+/// Signature of the call the cursor is in as JSON, or `null` when outside of any call
+#[wasm_bindgen]
+pub fn signature_help(code: &str, offset: usize) -> Result<String, String> {
+    let help = editor::signature_help(code, editor::byte_offset(code, offset));
+
+    match serde_json::to_string_pretty(&help) {
+        Ok(help) => Ok(help),
+        Err(err) => Err(format!("{:?}", err)),
+    }
+}
+
+/// Quick-fixes of the diagnostics touching the `start` to `end` range as JSON. Offsets count UTF-16
+/// code units, like JavaScript strings.
+#[wasm_bindgen]
+pub fn code_actions(code: &str, start: usize, end: usize) -> Result<String, String> {
+    let range = editor::SourceRange {
+        start: editor::byte_offset(code, start),
+        end: editor::byte_offset(code, end),
+    };
+    let actions = editor::code_actions(code, range)
+        .into_iter()
+        .map(|mut action| {
+            action.range = editor::SourceRange {
+                start: editor::utf16_offset(code, action.range.start),
+                end: editor::utf16_offset(code, action.range.end),
+            };
+            action
+        })
+        .collect::<Vec<editor::code_action::CodeAction>>();
+
+    match serde_json::to_string_pretty(&actions) {
+        Ok(actions) => Ok(actions),
+        Err(err) => Err(format!("{:?}", err)),
+    }
+}
@@ -0,0 +1,208 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
+};
+
+use eyre::Context;
+use notify::{RecursiveMode, Watcher};
+
+use crate::{
+    checker::{self, diagnostic::Diagnostic},
+    transpiler::{TranspileOptions, TranspilerOption},
+};
+
+use super::{export, katalon_project};
+
+/// How long to wait for more file events before processing them, as editors tend to save in several steps
+const DEBOUNCE_DURATION: Duration = Duration::from_millis(100);
+
+pub enum WatchEvent {
+    Transpiled {
+        source: PathBuf,
+        output: PathBuf,
+    },
+    Failed {
+        source: PathBuf,
+        diagnostics: Vec<Diagnostic>,
+    },
+    Removed {
+        source: PathBuf,
+    },
+    Errored {
+        source: PathBuf,
+        error: color_eyre::Report,
+    },
+}
+
+fn is_autalon_script(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "atln")
+}
+
+/// Test case name of a script, relative to the watched directory and without extension, e.g. `login/Admin Login`
+fn test_case_name(source_dir: &Path, path: &Path) -> Result<String, color_eyre::Report> {
+    let relative = path
+        .strip_prefix(source_dir)
+        .with_context(|| format!("'{}' is outside the watched directory", path.display()))?
+        .with_extension("");
+
+    Ok(relative
+        .components()
+        .map(|x| x.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+fn collect_scripts(dir: &Path, scripts: &mut Vec<PathBuf>) -> Result<(), color_eyre::Report> {
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read '{}'", dir.display()))?
+    {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            collect_scripts(&path, scripts)?;
+        } else if is_autalon_script(&path) {
+            scripts.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Re-transpile a single script into the Katalon project, or remove its output if the script is gone
+pub fn sync_script(
+    source_dir: &Path,
+    project_dir: &Path,
+    path: &Path,
+) -> Result<WatchEvent, color_eyre::Report> {
+    let name = test_case_name(source_dir, path)?;
+    let script_path = project_dir.join(katalon_project::script_path(&name));
+
+    let test_case_path = project_dir.join(katalon_project::test_case_path(&name));
+
+    if !path.exists() {
+        if script_path.exists() {
+            std::fs::remove_file(&script_path)?;
+        }
+        // Katalon may have put files of its own next to the script
+        if let Some(script_dir) = script_path.parent() {
+            if script_dir.exists() && std::fs::read_dir(script_dir)?.next().is_none() {
+                std::fs::remove_dir(script_dir)?;
+            }
+        }
+        if test_case_path.exists() {
+            std::fs::remove_file(&test_case_path)?;
+        }
+
+        return Ok(WatchEvent::Removed {
+            source: path.to_path_buf(),
+        });
+    }
+
+    let code = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read '{}'", path.display()))?;
+
+    let diagnostics = checker::check_program(&code);
+    if diagnostics.iter().any(Diagnostic::is_error) {
+        return Ok(WatchEvent::Failed {
+            source: path.to_path_buf(),
+            diagnostics,
+        });
+    }
+
+    let options = TranspileOptions {
+        file_name: path.file_name().map(|x| x.to_string_lossy().to_string()),
+        ..Default::default()
+    };
+    let script = export::transpile(&code, TranspilerOption::Groovy, &options)?;
+
+    if let Some(script_dir) = script_path.parent() {
+        std::fs::create_dir_all(script_dir)?;
+    }
+    std::fs::write(&script_path, script.code)?;
+
+    // Katalon keeps its own data in the test case file, so only create it once
+    if !test_case_path.exists() {
+        if let Some(test_case_dir) = test_case_path.parent() {
+            std::fs::create_dir_all(test_case_dir)?;
+        }
+        std::fs::write(&test_case_path, katalon_project::test_case_entity(&name))?;
+    }
+
+    Ok(WatchEvent::Transpiled {
+        source: path.to_path_buf(),
+        output: script_path,
+    })
+}
+
+/// Same as [`sync_script`], but failures only affect the script that caused them
+fn sync_script_reported(source_dir: &Path, project_dir: &Path, path: &Path) -> WatchEvent {
+    sync_script(source_dir, project_dir, path).unwrap_or_else(|error| WatchEvent::Errored {
+        source: path.to_path_buf(),
+        error,
+    })
+}
+
+/// Transpile every script under `source_dir` into the Katalon project at `project_dir`
+pub fn sync_all(
+    source_dir: &Path,
+    project_dir: &Path,
+) -> Result<Vec<WatchEvent>, color_eyre::Report> {
+    let mut scripts = vec![];
+    collect_scripts(source_dir, &mut scripts)?;
+
+    Ok(scripts
+        .iter()
+        .map(|path| sync_script_reported(source_dir, project_dir, path))
+        .collect())
+}
+
+/// Paths changed by a file event. A failing event is logged, as the watcher keeps working after it.
+fn event_paths(event: notify::Result<notify::Event>) -> Vec<PathBuf> {
+    match event {
+        Ok(event) => event.paths,
+        Err(err) => {
+            tracing::error!(err = err.to_string(), "Failed watching for changes");
+            vec![]
+        }
+    }
+}
+
+/// Watch `source_dir` for changes, only re-transpiling the scripts that changed.
+/// Blocks the current thread, calling `on_event` after every processed script.
+pub fn watch(
+    source_dir: &Path,
+    project_dir: &Path,
+    mut on_event: impl FnMut(WatchEvent),
+) -> Result<(), color_eyre::Report> {
+    // Changed paths are reported as absolute, so the prefix must be too
+    let source_dir = source_dir
+        .canonicalize()
+        .with_context(|| format!("Failed to open '{}'", source_dir.display()))?;
+
+    for event in sync_all(&source_dir, project_dir)? {
+        on_event(event);
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    watcher.watch(&source_dir, RecursiveMode::Recursive)?;
+
+    while let Ok(event) = receiver.recv() {
+        let mut changed_paths = event_paths(event);
+
+        // Collect whatever else happens during the debounce window
+        while let Ok(event) = receiver.recv_timeout(DEBOUNCE_DURATION) {
+            changed_paths.extend(event_paths(event));
+        }
+
+        changed_paths.sort();
+        changed_paths.dedup();
+
+        for path in changed_paths.iter().filter(|x| is_autalon_script(x)) {
+            on_event(sync_script_reported(&source_dir, project_dir, path));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use wasm_bindgen_test::*;
+
+use crate::checker::{self, diagnostic::Fix};
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_deprecated_alias() {
+    let code = "#:NavigateToUrl(\"https://a.b\");\n#:ClickElementByText(\"View\", \"*\");\n";
+    let diagnostics = checker::check_program(code);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(!diagnostics[0].is_error());
+    assert_eq!(
+        diagnostics[0].message,
+        "\"ClickElementByText\" is deprecated since 0.2.3, use \"ClickElementByString\" instead"
+    );
+    assert_eq!(
+        diagnostics[0].fixes,
+        vec![Fix {
+            title: "Replace with 'ClickElementByString'".to_string(),
+            start: code.find("#:Click").unwrap(),
+            end: code.len() - ";\n".len(),
+            replacement: "#:ClickElementByString(\"View\", ByOption.Text, \"*\", true)".to_string(),
+            preferred: true,
+        }]
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_transpile_deprecated_alias() -> color_eyre::eyre::Result<()> {
+    let script = crate::transpile_groovy(
+        "#:ClickElementByText(\"View\", \"*\");\n#:InputTextboxByLabelExact(\"id\", \"sysadmin\");",
+    )?;
+
+    assert!(script.contains(
+        "driver.getElement().byString(\"View\", ByOption.Text, \"*\", true).untilElementInteractable().click();"
+    ));
+    assert!(script.contains(
+        "extUIGetter.getInputFromLabel(\"id\").shouldBe().textbox().sendText(\"sysadmin\");"
+    ));
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_deprecated_alias_arguments() {
+    let diagnostics = checker::check_program("#:GetElementByText(\"Email\");\n");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0]
+        .message
+        .contains("Supplied argument count \"1\", expected argument count \"2\""));
+}
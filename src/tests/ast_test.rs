@@ -0,0 +1,141 @@
+use wasm_bindgen_test::*;
+
+use crate::ast::{self, BinaryOp, ExprKind, Literal, StatementKind, UnaryOp};
+use crate::checker::Checker;
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_build_arithmetic_precedence() -> color_eyre::eyre::Result<()> {
+    let program = ast::parse_program("var x = 1 + 2 * 3 ^ 2;")?;
+
+    let StatementKind::VarDeclaration { name, value, .. } = &program.statements[0].kind else {
+        panic!("Expected a variable declaration");
+    };
+    assert_eq!(name.name, "x");
+
+    let ExprKind::Binary { op, lhs, rhs } = &value.kind else {
+        panic!("Expected a binary expression");
+    };
+    assert_eq!(*op, BinaryOp::Add);
+    assert!(matches!(&lhs.kind, ExprKind::Literal(Literal::Number(x)) if x == "1"));
+    assert!(matches!(
+        &rhs.kind,
+        ExprKind::Binary {
+            op: BinaryOp::Mul,
+            ..
+        }
+    ));
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_checker_annotates_types() -> color_eyre::eyre::Result<()> {
+    let mut program = ast::parse_program(
+        "#:GetElementByString(\"btnLogin\", ByOption.ID, \"*\", true).getText();",
+    )?;
+    let statement = &mut program.statements[0];
+
+    Checker::new().check_statement(statement)?;
+
+    let StatementKind::Expr(expr) = &statement.kind else {
+        panic!("Expected an expression");
+    };
+    let ExprKind::MethodCall { receiver, .. } = &expr.kind else {
+        panic!("Expected a method call");
+    };
+    assert_eq!(expr.ty, Some("string"));
+    assert_eq!(receiver.ty, Some("element"));
+    assert_eq!(statement.span.line, 1);
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_build_unary_and_parentheses() -> color_eyre::eyre::Result<()> {
+    let program = ast::parse_program("var x = -2 ^ 2 * (1 + 3) > 4 && !false;")?;
+
+    let StatementKind::VarDeclaration { value, .. } = &program.statements[0].kind else {
+        panic!("Expected a variable declaration");
+    };
+    let ExprKind::Binary {
+        op: BinaryOp::And,
+        lhs: comparison,
+        rhs: not,
+    } = &value.kind
+    else {
+        panic!("Expected && to bind loosest");
+    };
+    assert!(matches!(
+        &not.kind,
+        ExprKind::Unary {
+            op: UnaryOp::Not,
+            ..
+        }
+    ));
+
+    let ExprKind::Binary {
+        op: BinaryOp::Gt,
+        lhs: product,
+        ..
+    } = &comparison.kind
+    else {
+        panic!("Expected a comparison");
+    };
+    let ExprKind::Binary {
+        op: BinaryOp::Mul,
+        lhs: negation,
+        rhs: group,
+    } = &product.kind
+    else {
+        panic!("Expected a product");
+    };
+    // Power binds tighter than negation
+    assert!(matches!(
+        &negation.kind,
+        ExprKind::Unary {
+            op: UnaryOp::Neg,
+            operand
+        } if matches!(operand.kind, ExprKind::Binary { op: BinaryOp::Pow, .. })
+    ));
+    assert!(matches!(
+        &group.kind,
+        ExprKind::Binary {
+            op: BinaryOp::Add,
+            ..
+        }
+    ));
+    assert_eq!(group.span.start, "var x = -2 ^ 2 * ".len());
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_transpile_parenthesizes_for_groovy() -> color_eyre::eyre::Result<()> {
+    let script = crate::transpile_groovy(
+        "var x = (1 + 2) * -3;\nvar y = 2 ^ 3 ^ 2;\nvar z = !(true && false) || (1 < 2);\n",
+    )?;
+
+    assert!(script.contains("def x = (1 + 2) * -3;"));
+    // Groovy groups powers from the left
+    assert!(script.contains("def y = 2 ** (3 ** 2);"));
+    assert!(script.contains("def z = !(true && false) || 1 < 2;"));
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_transpile_decimals_as_bigdecimal() -> color_eyre::eyre::Result<()> {
+    let script = crate::transpile_groovy(
+        "var price = 12.50 * 2;\n#:ExtUIInputNumberTextboxByLabelExact(\"Amount\", 1E3);\n",
+    )?;
+
+    assert!(script.contains("def price = 12.50G * 2;"));
+    assert!(script.contains("sendText((1e3G as BigDecimal).toPlainString())"));
+
+    Ok(())
+}
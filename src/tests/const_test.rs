@@ -0,0 +1,69 @@
+use wasm_bindgen_test::*;
+
+use crate::{
+    checker::{self, diagnostic::Diagnostic},
+    formatter,
+};
+
+/// Checking errors, without the lint warnings
+fn errors(code: &str) -> Vec<Diagnostic> {
+    checker::check_program(code)
+        .into_iter()
+        .filter(Diagnostic::is_error)
+        .collect()
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_const_declaration() -> color_eyre::eyre::Result<()> {
+    let code = "const BASE_URL = \"https://a.b\";\nconst PAGE = \"${BASE_URL}/login\";\n#:NavigateToUrl(PAGE);\n";
+
+    assert!(checker::check_program(code).is_empty());
+    assert_eq!(formatter::format_program(code)?, code);
+
+    let script = crate::transpile_groovy(code)?;
+    assert!(script.contains("final BASE_URL = \"https://a.b\";"));
+    assert!(script.contains("final PAGE = \"${BASE_URL}/login\";"));
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_const_reassignment() {
+    let diagnostics = errors("const RETRIES = 2 * 3;\nRETRIES = 7;\n");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0]
+        .message
+        .contains("Cannot assign to constant \"RETRIES\""));
+    assert_eq!(diagnostics[0].line, 2);
+
+    // Variables stay assignable
+    assert!(errors("var retries = 2;\nretries = 7;\n").is_empty());
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_const_not_constant() {
+    let diagnostics = errors(
+        "var x = 1;\nconst Y = x + 1;\nconst EL = #:GetElementByString(\"a\", ByOption.ID, \"*\", true);\n",
+    );
+
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics[0]
+        .message
+        .contains("constant \"Y\" must be made of literals and other constants"));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_const_in_cfg() {
+    let code = "#[coercion=MODE]\nconst MODE = \"concat\";\n#:NavigateToUrl(\"Row \" + 3);\n";
+    assert!(checker::check_program(code).is_empty());
+
+    let code = "#[coercion=MODE]\nconst MODE = \"loose\";\n";
+    assert!(checker::check_program(code)[0]
+        .message
+        .contains("Unknown coercion 'loose'"));
+}
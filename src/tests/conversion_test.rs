@@ -0,0 +1,82 @@
+use wasm_bindgen_test::*;
+
+use crate::autalonparser::{AutalonParser, Rule};
+use crate::{ast, checker, formatter};
+use pest::Parser;
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_conversion_returntype() -> color_eyre::eyre::Result<()> {
+    let mut checker = checker::Checker::new();
+
+    for (code, expected) in [
+        ("(1 + 2).toString()", "string"),
+        ("(\"12\").toNumber()", "number"),
+        ("(1.5).toNumber()", "number"),
+        ("(\"true\").toBool()", "bool"),
+        ("\"12\".toNumber()", "number"),
+        ("3.toString()", "string"),
+        ("1.5.toNumber()", "number"),
+        ("true.toString()", "string"),
+    ] {
+        let mut parsed = AutalonParser::parse(Rule::expr, code)?;
+        let mut expr = ast::build_expr(parsed.next().unwrap())?;
+        assert_eq!(checker.get_expr_returntype(&mut expr)?, expected);
+    }
+
+    let mut invalid = AutalonParser::parse(Rule::expr, "(true).toNumber()")?;
+    let err = checker
+        .get_expr_returntype(&mut ast::build_expr(invalid.next().unwrap())?)
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("can't be converted with 'toNumber'"));
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_coercion_policy() {
+    let strict = checker::check_program("#:NavigateToUrl(\"Row \" + 3);");
+    assert_eq!(strict.len(), 1);
+    assert!(strict[0].message.contains("#[coercion=concat]"));
+
+    assert!(
+        checker::check_program("#[coercion=concat]\n#:NavigateToUrl(\"Row \" + 3);").is_empty()
+    );
+    assert!(checker::check_program("#:NavigateToUrl(\"Row \" + (3).toString());").is_empty());
+
+    let unknown = checker::check_program("#[coercion=loose]\n#:NavigateToUrl(\"Row\");");
+    assert_eq!(unknown.len(), 1);
+    assert!(unknown[0].message.contains("Unknown coercion 'loose'"));
+    assert_eq!(unknown[0].line, 1);
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_transpile_conversions() -> color_eyre::eyre::Result<()> {
+    let script = crate::transpile_groovy(
+        "#[coercion=concat]\n#:NavigateToUrl(\"Row \" + 3 + \", \" + 1.5 + (2 * 2).toString());",
+    )?;
+    assert!(script.contains(
+        "\"Row \" + String.valueOf(3) + \", \" + (1.5G as BigDecimal).toPlainString() + String.valueOf((2 * 2))"
+    ));
+
+    let script = crate::transpile_groovy("#:NavigateToUrl((\"4\").toNumber().toString());")?;
+    assert!(script.contains("String.valueOf(Integer.parseInt(\"4\"))"));
+
+    let script = crate::transpile_groovy("#:NavigateToUrl(\"4\".toNumber().toString());")?;
+    assert!(script.contains("String.valueOf(Integer.parseInt(\"4\"))"));
+
+    assert_eq!(
+        formatter::format_program("var x = ( 1+2 ).toString();")?,
+        "var x = (1 + 2).toString();\n"
+    );
+    assert_eq!(
+        formatter::format_program("var x = \"12\" .toNumber();")?,
+        "var x = \"12\".toNumber();\n"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use strum::IntoEnumIterator;
+use wasm_bindgen_test::*;
+
+use crate::{
+    builtin_package_definition::{get_fn_metadata, BuiltinPkgFunctions, DriverVersion},
+    checker, editor,
+    transpiler::katalon_prealpha::pkgdef,
+};
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_transpile_pinned_driver() -> color_eyre::eyre::Result<()> {
+    let code = "#:ClickElementByString(\"btnLogin\", ByOption.ID, \"*\", true);\n";
+
+    let latest = crate::transpile_groovy(code)?;
+    assert!(latest.contains(
+        "driver.getElement().byString(\"btnLogin\", ByOption.ID, \"*\", true).untilElementInteractable().click();"
+    ));
+    assert!(latest.contains("def reactMUIGetter = driver.uiGetter().reactMUI()"));
+
+    let pinned = crate::transpile_groovy(&format!("#[driver_ver=0.1.1]\n{code}"))?;
+    assert!(pinned
+        .contains("driver.getElement().byString(\"btnLogin\", ByOption.ID, \"*\", true).click();"));
+    assert!(!pinned.contains("reactMUIGetter"));
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_call_missing_in_pinned_driver() {
+    let code = "#[driver_ver=0.1.1]\n#:MUIInputTextboxByLabelExact(\"Name\", \"a\");\n";
+    let diagnostics = checker::check_program(code);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "\"MUIInputTextboxByLabelExact\" needs driver 0.2.0 or newer, but the script declares #[driver_ver=0.1.1]"
+    );
+    assert_eq!(diagnostics[0].line, 2);
+
+    // Not offered by the completion either
+    let items = editor::complete(code, code.find("MUI").unwrap());
+    assert!(!items.is_empty());
+    assert!(!items.iter().any(|x| x.label.starts_with("MUI")));
+
+    let unpinned = &code["#[driver_ver=0.1.1]\n".len()..];
+    let items = editor::complete(unpinned, unpinned.find("MUI").unwrap());
+    assert!(items.iter().any(|x| x.label.starts_with("MUI")));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_unknown_driver_version() {
+    let diagnostics =
+        checker::check_program("#[driver_ver=9.9]\n#:NavigateToUrl(\"https://a.b\");\n");
+
+    assert_eq!(
+        diagnostics[0].message,
+        "Unknown driver version '9.9', expected one of 0.1.1, 0.2.0"
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_templates_match_since_driver() {
+    for builtin_fn in BuiltinPkgFunctions::iter() {
+        let metadata = get_fn_metadata(&builtin_fn);
+
+        for driver in DriverVersion::iter() {
+            assert_eq!(
+                pkgdef::get_default_fn_template(&builtin_fn.to_string(), "#", driver).is_ok(),
+                metadata.since_driver <= driver,
+                "{builtin_fn} with driver {driver}"
+            );
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use wasm_bindgen_test::*;
+
+use crate::editor::{self, completion::CompletionKind, SourceRange};
+
+const SCRIPT: &str =
+    "var el = #:GetElementByString(\"a\", ByOption.ID, \"*\", true);\nel.click();\n";
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_complete_builtin_functions() {
+    let items = editor::complete("#:Navig", 7);
+
+    assert_eq!(
+        items
+            .iter()
+            .map(|x| x.label.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["NavigateToUrl"]
+    );
+
+    // Labelled with the name the builtin is called by, not its display name
+    let code = "#:ExtUIGetG";
+    let items = editor::complete(code, code.len());
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].label, "ExtUIGetGroupFromLabel");
+
+    let code = "#:ExtUIGetGroupFromLabel(";
+    assert!(editor::hover(code, 4).is_some());
+    assert!(editor::signature_help(code, code.len()).is_some());
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_complete_enum_members_and_element_methods() {
+    let enum_members = editor::complete("ByOption.", 9);
+    assert!(enum_members.iter().any(|x| x.label == "XPath"));
+
+    let code = format!("{SCRIPT}el.");
+    let methods = editor::complete(&code, code.len());
+    assert!(methods.iter().any(|x| x.label == "sendText"));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_complete_conversion_methods() {
+    let labels = |code: &str| {
+        editor::complete(code, code.len())
+            .into_iter()
+            .map(|x| x.label)
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(
+        labels("var x = \"12\".to"),
+        vec!["toString", "toNumber", "toBool"]
+    );
+    assert_eq!(labels("var x = true.to"), vec!["toString", "toBool"]);
+    assert_eq!(
+        labels("var n = 3;\nvar x = n.to"),
+        vec!["toString", "toNumber"]
+    );
+    assert!(labels("var x = (1 + 2).").contains(&"toString".to_string()));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_signature_help_active_parameter() {
+    let code = "#:SendTextToElementByString(\"a\", \"b\", ";
+    let help = editor::signature_help(code, code.len()).unwrap();
+
+    assert_eq!(help.active_parameter, 2);
+    assert_eq!(help.parameters[2].label, "By Option: byoption");
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_hover_and_definition_of_variable() {
+    let usage = SCRIPT.rfind("el.click").unwrap();
+
+    assert_eq!(
+        editor::definition(SCRIPT, usage),
+        Some(SourceRange { start: 4, end: 6 })
+    );
+    assert!(editor::hover(SCRIPT, usage)
+        .unwrap()
+        .contents
+        .contains("var el: element"));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_complete_builtins_of_declared_ui() {
+    let code = "#[ui=mui]\n#:";
+    let items = editor::complete(code, code.len());
+
+    assert!(items
+        .iter()
+        .any(|x| x.label == "MUIInputRadioUsingTextByLabelExact"));
+    assert!(items.iter().any(|x| x.label == "NavigateToUrl"));
+    assert!(!items
+        .iter()
+        .any(|x| x.label == "ExtUIInputDateByLabelExact"));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_complete_builtins_of_ui_constant() {
+    // The cfg value names a constant, like the checker allows
+    let code = "#[ui=UI]\nconst UI = \"ext\";\n#:";
+    let items = editor::complete(code, code.len());
+
+    assert!(items
+        .iter()
+        .any(|x| x.label == "ExtUIInputDateByLabelExact"));
+    assert!(!items
+        .iter()
+        .any(|x| x.label == "MUIInputRadioUsingTextByLabelExact"));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_complete_keywords() {
+    let labels = |code: &str| {
+        editor::complete(code, code.len())
+            .into_iter()
+            .filter(|x| x.kind == CompletionKind::Keyword)
+            .map(|x| x.label)
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(labels("co"), vec!["const"]);
+    assert_eq!(labels(""), vec!["var", "const"]);
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_utf16_offset_round_trip() {
+    let code = "var s = \"日本\";\nvar x = 1;";
+    let byte = code.find('x').unwrap();
+    let utf16 = editor::utf16_offset(code, byte);
+
+    assert_eq!(utf16, 18);
+    assert_eq!(editor::byte_offset(code, utf16), byte);
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_offset_inside_multibyte_char() {
+    let code = "var x = \"é\";\n#:NavigateToUrl(x);\n";
+    // Between the two bytes of "é"
+    let offset = code.find('é').unwrap() + 1;
+
+    editor::complete(code, offset);
+    assert!(editor::hover(code, offset).is_none());
+    assert!(editor::signature_help(code, offset).is_none());
+    editor::code_actions(
+        code,
+        SourceRange {
+            start: offset,
+            end: offset,
+        },
+    );
+    assert_eq!(editor::utf16_offset(code, offset), 9);
+}
@@ -0,0 +1,135 @@
+use wasm_bindgen_test::*;
+
+use crate::{
+    checker,
+    fixer::{self, SCRIPT_VERSION},
+};
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_fix_deprecated_builtin() -> color_eyre::eyre::Result<()> {
+    let fixed = fixer::fix_program(
+        "#[version=1]\n#:ClickElementByText(\"Login\", \"*\");\n",
+        "login.atln",
+    )?;
+
+    assert_eq!(
+        fixed.code,
+        format!(
+            "#[version={SCRIPT_VERSION}]\n#:ClickElementByString(\"Login\", ByOption.Text, \"*\", true);\n"
+        )
+    );
+    assert_eq!(
+        fixed.applied,
+        vec![
+            "Replace with 'ClickElementByString'".to_string(),
+            format!("Set the version to {SCRIPT_VERSION}")
+        ]
+    );
+    assert!(checker::check_program(&fixed.code).is_empty());
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_fix_arguments() -> color_eyre::eyre::Result<()> {
+    // A missing default argument, and arguments that each fit a single parameter
+    let code = "#:ClickElementByString(\"btnLogin\", ByOption.ID, \"*\");\n#:Assert(FailureHandling.Stop, true);\n";
+    let fixed = fixer::fix_program(code, "login.atln")?;
+
+    assert!(fixed
+        .code
+        .contains("#:ClickElementByString(\"btnLogin\", ByOption.ID, \"*\", true);\n"));
+    assert!(fixed
+        .code
+        .contains("#:Assert(true, FailureHandling.Stop);\n"));
+    assert!(checker::check_program(&fixed.code).is_empty());
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_fix_ambiguous_arguments_suggested() -> color_eyre::eyre::Result<()> {
+    // The strings could go in any of the string parameters
+    let code = "#:SendTextToElementByString(\"txtUser\", ByOption.ID, \"input\", true);\n";
+
+    let diagnostics = checker::check_program(code);
+    assert_eq!(diagnostics[0].fixes.len(), 1);
+    assert!(!diagnostics[0].fixes[0].preferred);
+    assert_eq!(
+        diagnostics[0].fixes[0].replacement,
+        "#:SendTextToElementByString(\"txtUser\", \"input\", ByOption.ID, \"*\", true)"
+    );
+
+    // Left as is by the fix command
+    let fixed = fixer::fix_program(code, "login.atln")?;
+    assert!(fixed.code.ends_with(code));
+    assert!(fixed.applied.iter().all(|x| !x.starts_with("Match")));
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_fix_diff() -> color_eyre::eyre::Result<()> {
+    let code = format!("#[version={SCRIPT_VERSION}]\n#:NavigateToUrl(\"https://a.b\");\n#:GetElementByText(\"Add\", \"*\");\n");
+    let fixed = fixer::fix_program(&code, "add.atln")?;
+
+    assert_eq!(
+        fixed.diff,
+        "--- a/add.atln\n+++ b/add.atln\n@@ -1,3 +1,3 @@\n #[version=0.2.3]\n #:NavigateToUrl(\"https://a.b\");\n-#:GetElementByText(\"Add\", \"*\");\n+#:GetElementByString(\"Add\", ByOption.Text, \"*\", true);\n"
+    );
+
+    // Fixing again changes nothing
+    let again = fixer::fix_program(&fixed.code, "add.atln")?;
+    assert_eq!(again.code, fixed.code);
+    assert!(again.diff.is_empty());
+    assert!(again.applied.is_empty());
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_fix_unfittable_arguments() -> color_eyre::eyre::Result<()> {
+    // Nothing to put in for the required text
+    let diagnostics = checker::check_program("#:ExtUIInputTextboxByLabelExact(\"id\");\n");
+    assert!(diagnostics[0].fixes.is_empty());
+
+    let fixed = fixer::fix_program("#:ExtUIInputTextboxByLabelExact(\"id\");\n", "id.atln")?;
+    assert_eq!(
+        fixed.code,
+        format!("#[version={SCRIPT_VERSION}]\n#:ExtUIInputTextboxByLabelExact(\"id\");\n")
+    );
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_fix_numbers_given_as_strings() -> color_eyre::eyre::Result<()> {
+    // Written when the number textbox took its value as a string
+    let code = "#[version=0.2.0]\n#:ExtUIInputNumberTextboxByLabelExact(\"Amount\", \"12.50\");\n";
+
+    let diagnostics = checker::check_program(code);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].fixes[0].preferred);
+
+    let fixed = fixer::fix_program(code, "invoice.atln")?;
+    assert_eq!(
+        fixed.code,
+        format!(
+            "#[version={SCRIPT_VERSION}]\n#:ExtUIInputNumberTextboxByLabelExact(\"Amount\", 12.50);\n"
+        )
+    );
+    assert!(checker::check_program(&fixed.code).is_empty());
+
+    // Text that isn't a number is left for the author
+    let diagnostics =
+        checker::check_program("#:ExtUIInputNumberTextboxByLabelExact(\"Amount\", \"twelve\");\n");
+    assert!(diagnostics[0].fixes.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use wasm_bindgen_test::*;
+
+use crate::formatter::format_program;
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_format_spacing_and_comments() -> color_eyre::eyre::Result<()> {
+    let code = "/* header */\n#[ui=mui]\nvar x=1+  2 ;   x = x*2; // trailing\n\n\n#:Assert( x  <  3 ,/* why */ FailureHandling.Stop);\n";
+
+    assert_eq!(
+        format_program(code)?,
+        "/* header */\n#[ui=mui]\n\nvar x = 1 + 2;\nx = x * 2; // trailing\n\n/* why */\n#:Assert(x < 3, FailureHandling.Stop);\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_format_wraps_long_arguments() -> color_eyre::eyre::Result<()> {
+    let code = "#:SendTextToElementByString(\"some very long value that goes past the limit\", \"another long argument\", ByOption.XPath, \"*\", false);";
+    let formatted = format_program(code)?;
+
+    assert_eq!(
+        formatted,
+        "#:SendTextToElementByString(\n    \"some very long value that goes past the limit\",\n    \"another long argument\",\n    ByOption.XPath,\n    \"*\",\n    false\n);\n"
+    );
+    assert_eq!(format_program(&formatted)?, formatted);
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_format_keeps_needed_parentheses() -> color_eyre::eyre::Result<()> {
+    let code = "var x = ((1 + 2)) * (3 * 4) - (-(5));\nvar y = (2 ^ 3) ^ 2 + - -1;\n";
+
+    assert_eq!(
+        format_program(code)?,
+        "var x = (1 + 2) * (3 * 4) - -5;\nvar y = (2 ^ 3) ^ 2 + -(-1);\n"
+    );
+
+    Ok(())
+}
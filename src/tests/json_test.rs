@@ -0,0 +1,76 @@
+use wasm_bindgen_test::*;
+
+use crate::ast::json::parse_to_json;
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_parse_to_json_schema() -> color_eyre::eyre::Result<()> {
+    let json = serde_json::to_value(parse_to_json(
+        "#[ui=mui]\n#:NavigateToUrl(\"https://a.b\"); // go\nvar x = 1 + 2;\n",
+    )?)?;
+
+    assert_eq!(json["schemaVersion"], 2);
+    assert_eq!(json["program"]["cfg"][0]["key"], "ui");
+    assert_eq!(json["program"]["cfg"][0]["value"], "mui");
+
+    let call = &json["program"]["statements"][0];
+    assert_eq!(call["kind"], "expr");
+    assert_eq!(call["span"]["line"], 2);
+    assert_eq!(call["trivia"]["trailing"][0]["content"], " go");
+    assert_eq!(call["node"]["kind"], "call");
+    assert_eq!(call["node"]["node"]["package"], "#");
+    assert_eq!(call["node"]["node"]["builtin"], "NavigateToUrl");
+    assert_eq!(
+        call["node"]["node"]["args"][0]["node"]["string"],
+        "https://a.b"
+    );
+    assert_eq!(call["node"]["node"]["args"][0]["type"], "string");
+
+    let declaration = &json["program"]["statements"][1];
+    assert_eq!(declaration["kind"], "varDeclaration");
+    assert_eq!(declaration["node"]["name"]["name"], "x");
+    assert_eq!(declaration["node"]["value"]["node"]["op"], "+");
+    assert_eq!(declaration["node"]["value"]["type"], "number");
+
+    // Lint warnings are listed with the checking problems
+    assert_eq!(json["diagnostics"].as_array().map(Vec::len), Some(1));
+    assert_eq!(json["diagnostics"][0]["code"], "unused_variable");
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_parse_to_json_keeps_unchecked_statements() -> color_eyre::eyre::Result<()> {
+    let json = serde_json::to_value(parse_to_json("#:NavigateToUrl(1);")?)?;
+
+    assert_eq!(
+        json["program"]["statements"][0]["node"]["type"],
+        serde_json::Value::Null
+    );
+    assert_eq!(json["diagnostics"][0]["line"], 1);
+    assert_eq!(json["diagnostics"][0]["severity"], "error");
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_parse_to_json_reports_cfg_errors() -> color_eyre::eyre::Result<()> {
+    let json = serde_json::to_value(parse_to_json(
+        "#[ui=foo]\nvar url = \"https://a.b\";\n#:NavigateToUrl(url);",
+    )?)?;
+
+    assert_eq!(
+        json["diagnostics"][0]["message"],
+        "Unknown UI framework 'foo', expected 'ext', 'mui' or 'any'"
+    );
+    assert_eq!(json["diagnostics"][0]["line"], 1);
+    // The statements are still checked
+    assert_eq!(
+        json["program"]["statements"][1]["node"]["node"]["args"][0]["type"],
+        "string"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use wasm_bindgen_test::*;
+
+use crate::checker::{self, diagnostic::Diagnostic, lint};
+
+/// `(line, rule)` of the lint warnings of a script
+fn lints(code: &str) -> Vec<(usize, &'static str)> {
+    checker::check_program(code)
+        .iter()
+        .filter_map(|x: &Diagnostic| x.code.map(|code| (x.line, code)))
+        .collect()
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_lint_variables() {
+    assert_eq!(
+        lints("var unused = 1;\nvar unread = 2;\nunread = 3;\nvar read = 4;\n#:Assert(read > 0, FailureHandling.Stop);\n"),
+        vec![(1, "unused_variable"), (2, "unread_variable")]
+    );
+
+    // Constants named by cfg values are used
+    assert!(lints("#[coercion=MODE]\nconst MODE = \"concat\";\n").is_empty());
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_lint_statements() {
+    let code = "#:NavigateToUrl(\"https://a.b\");\n#:NavigateToUrl(\"https://c.d\");\n#:SendTextToElementByString(\"txtPassword\", \"superuser\", ByOption.ID, \"input\", true);\n#:Assert(false, FailureHandling.Stop);\n#:NavigateToUrl(\"https://e.f\");\n";
+
+    assert_eq!(
+        lints(code),
+        vec![
+            (5, "unreachable_code"),
+            (2, "duplicate_navigation"),
+            (3, "hardcoded_credential"),
+        ]
+    );
+
+    // Elements typed into, and textboxes found by their label
+    let code = "var el = #:GetElementByString(\"txtPassword\", ByOption.ID, \"*\", true);\nel.sendText(\"hunter2\");\n#:ExtUIInputTextboxByLabelExact(\"Password\", \"hunter2\");\n#:MUIInputTextboxByLabelExact(\"API token\", \"abc\");\n#:InputTextboxByLabelExact(\"pass\", \"superuser\");\n#:ExtUIInputTextboxByLabelExact(\"Name\", \"Bob\");\nel = #:GetElementByString(\"txtName\", ByOption.ID, \"*\", true);\nel.sendText(\"Bob\");\n";
+    assert_eq!(
+        lints(code)
+            .into_iter()
+            .filter(|(_, rule)| *rule == "hardcoded_credential")
+            .map(|(line, _)| line)
+            .collect::<Vec<usize>>(),
+        vec![2, 3, 4, 5]
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_lint_unreachable_after_default_failure_handling() {
+    // The failure handling left out stops the test, like `FailureHandling.Stop`
+    let code = "#:Assert(false);\n#:NavigateToUrl(\"https://a.b\");\n";
+    assert_eq!(lints(code), vec![(2, "unreachable_code")]);
+
+    let code = "#:Assert(false, FailureHandling.Continue);\n#:NavigateToUrl(\"https://a.b\");\n";
+    assert!(lints(code).is_empty());
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_lint_mixed_ui() {
+    let code = "#:ExtUIInputTextboxByLabelExact(\"Name\", \"a\");\n#:MUIInputTextboxByLabelExact(\"Name\", \"b\");\n";
+    let diagnostics = checker::check_program(code);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, Some("mixed_ui"));
+    assert_eq!(
+        diagnostics[0].message,
+        "\"MUIInputTextboxByLabelExact\" is a MUI function, but \"ExtUIInputTextboxByLabelExact\" on line 1 is a Ext function"
+    );
+
+    // Builtins whose display name differs from the one they're called by are counted too
+    let code = "var group = #:ExtUIGetGroupFromLabel(\"Name\");\n#:MUIInputTextboxByLabelExact(\"Name\", \"b\");\ngroup.click();\n";
+    let diagnostics = checker::check_program(code);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, Some("mixed_ui"));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_lint_allow() {
+    let code = "var a = 1; // atln-allow(unused_variable)\n#:NavigateToUrl(\"https://a.b\");\n// atln-allow(duplicate_navigation, unused_variable)\n#:NavigateToUrl(\"https://c.d\");\nvar b = 2;\n";
+    assert_eq!(lints(code), vec![(5, "unused_variable")]);
+
+    assert!(lints(&format!("#[allow=unused_variable]\n{code}")).is_empty());
+    assert!(lints("#[allow=*]\nvar a = 1;\n").is_empty());
+
+    let unknown = checker::check_program("#[allow=everything]\n");
+    assert_eq!(unknown[0].message, "Unknown lint rule 'everything'");
+
+    assert_eq!(
+        lint::parse_allow_comment(" atln-allow( mixed_ui ,nope)"),
+        vec![lint::LintRule::MixedUi]
+    );
+}
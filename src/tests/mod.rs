@@ -1 +1,19 @@
+pub mod alias_test;
+pub mod ast_test;
+pub mod const_test;
+pub mod conversion_test;
+pub mod driver_test;
+pub mod editor_test;
+pub mod fix_test;
+pub mod formatter_test;
+pub mod json_test;
+pub mod lint_test;
+pub mod scope_test;
+pub mod source_map_test;
+pub mod string_test;
+pub mod suggestion_test;
+pub mod target_ui_test;
+pub mod trivia_test;
 pub mod wasm_test;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod watch_test;
@@ -0,0 +1,69 @@
+use wasm_bindgen_test::*;
+
+use crate::{ast, checker};
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_variables_across_statements() {
+    assert!(checker::check_program(
+        "var x = 1;\nvar y = x + 1;\ny = x * 2;\n#:Assert(y > x, FailureHandling.Stop);\n"
+    )
+    .is_empty());
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_redeclaration_points_at_declaration() -> color_eyre::eyre::Result<()> {
+    let diagnostics = checker::check_program("#[allow=unused_variable]\nvar x = 1;\nvar x = 2;\n");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "Variable \"x\" is already declared");
+    assert_eq!((diagnostics[0].line, diagnostics[0].column), (3, 5));
+    assert_eq!(diagnostics[0].related.len(), 1);
+    assert_eq!(
+        (
+            diagnostics[0].related[0].line,
+            diagnostics[0].related[0].column
+        ),
+        (2, 5)
+    );
+
+    let json = serde_json::to_value(&diagnostics[0])?;
+    assert_eq!(
+        json["related"][0]["message"],
+        "\"x\" is first declared here"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_nested_scope_shadowing() -> color_eyre::eyre::Result<()> {
+    let program = ast::parse_program(
+        "var x = \"outer\";\nconst LIMIT = 3;\nvar x = 1;\nx = 2;\nvar LIMIT = 4;\nx = \"again\";\n",
+    )?;
+    let mut statements = program.statements;
+    let mut checker = checker::Checker::new();
+
+    checker.check_statement(&mut statements[0])?;
+    checker.check_statement(&mut statements[1])?;
+
+    checker.enter_scope();
+    checker.check_statement(&mut statements[2])?;
+    checker.check_statement(&mut statements[3])?;
+    assert_eq!(checker.warnings.len(), 1);
+    assert_eq!(
+        checker.warnings[0].message,
+        "Variable \"x\" shadows the one declared on line 1"
+    );
+
+    let err = checker.check_statement(&mut statements[4]).unwrap_err();
+    assert_eq!(err.to_string(), "Constant \"LIMIT\" can't be shadowed");
+    checker.exit_scope();
+
+    // The outer string variable is visible again
+    checker.check_statement(&mut statements[5])?;
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use wasm_bindgen_test::*;
+
+use crate::ast;
+use crate::transpiler::{self, source_map::Transpiled, TranspileOptions, TranspilerOption};
+
+const SCRIPT: &str = "// Open the app\n#:NavigateToUrl(\"https://a.b\");\n\nvar x = 1 + 2;\n";
+
+fn transpile(options: &TranspileOptions) -> color_eyre::eyre::Result<Transpiled> {
+    let program = ast::parse_program(SCRIPT)?;
+
+    transpiler::program_handler(TranspilerOption::Groovy, &program, options)
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_source_map_points_at_script_lines() -> color_eyre::eyre::Result<()> {
+    let transpiled = transpile(&TranspileOptions::default())?;
+    let lines = transpiled.code.lines().collect::<Vec<&str>>();
+
+    let navigate_line = lines.iter().position(|x| x.contains("navigate")).unwrap() + 1;
+    let declaration_line = lines.iter().position(|x| x.contains("def x")).unwrap() + 1;
+
+    assert_eq!(transpiled.source_map.source_line(navigate_line), Some(2));
+    assert_eq!(transpiled.source_map.source_line(declaration_line), Some(4));
+    // The comment and the boilerplate don't come from a statement
+    assert_eq!(transpiled.source_map.source_line(navigate_line - 1), None);
+    assert_eq!(transpiled.source_map.source_line(1), None);
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_line_markers_and_stack_trace() -> color_eyre::eyre::Result<()> {
+    let transpiled = transpile(&TranspileOptions {
+        file_name: Some("login.atln".to_string()),
+        line_markers: true,
+    })?;
+
+    assert!(transpiled.code.contains("def x = 1 + 2; // atln:4"));
+
+    let declaration_line = transpiled
+        .code
+        .lines()
+        .position(|x| x.contains("def x"))
+        .unwrap()
+        + 1;
+    let trace = format!(
+        "\tat Script1665.run(Script1665.groovy:{declaration_line})\n\tat Other.run(Other.groovy:3)"
+    );
+
+    assert_eq!(
+        transpiled
+            .source_map
+            .translate_stack_trace(&trace, Some("Script1665.groovy")),
+        "\tat Script1665.run(login.atln:4)\n\tat Other.run(Other.groovy:3)"
+    );
+
+    Ok(())
+}
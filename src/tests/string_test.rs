@@ -0,0 +1,64 @@
+use wasm_bindgen_test::*;
+
+use crate::autalonparser::{AutalonParser, Rule};
+use crate::transpiler::escape;
+use crate::{ast, checker, formatter};
+use pest::Parser;
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_transpile_keeps_strings_literal() -> color_eyre::eyre::Result<()> {
+    let script = crate::transpile_groovy(
+        "#:NavigateToUrl(\"https://a.b/$price/\\u00e9\\\"q\\\"\\/\\uD83D\\uDE00\");",
+    )?;
+
+    assert!(script.contains("driver.navigate().to(\"https://a.b/\\$price/é\\\"q\\\"/😀\")"));
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_interpolation() -> color_eyre::eyre::Result<()> {
+    let code = "#:NavigateToUrl(\"https://a.b/${ 1+2 }?q=\\${x}&ok=${true}\");";
+
+    let script = crate::transpile_groovy(code)?;
+    assert!(script.contains("driver.navigate().to(\"https://a.b/${1 + 2}?q=\\${x}&ok=${true}\")"));
+
+    assert_eq!(
+        formatter::format_program(code)?,
+        "#:NavigateToUrl(\"https://a.b/${1 + 2}?q=\\${x}&ok=${true}\");\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_interpolation_type() -> color_eyre::eyre::Result<()> {
+    let mut checker = checker::Checker::new();
+
+    let mut missing = AutalonParser::parse(Rule::expr, "\"Hello ${name}\"")?;
+    assert!(checker
+        .get_expr_returntype(&mut ast::build_expr(missing.next().unwrap())?)
+        .is_err());
+
+    let mut void = AutalonParser::parse(Rule::expr, "\"${#:GetAndSwitchToAnyIFrame()}\"")?;
+    let err = checker
+        .get_expr_returntype(&mut ast::build_expr(void.next().unwrap())?)
+        .unwrap_err();
+    assert!(err.to_string().contains("can't be put into a string"));
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_escape_groovy_strings() {
+    let value = "a\"b\\c$d\ne\u{1}\u{2028}";
+
+    assert_eq!(
+        escape::quote(value),
+        "\"a\\\"b\\\\c\\$d\\ne\\u0001\u{2028}\""
+    );
+}
@@ -0,0 +1,109 @@
+use wasm_bindgen_test::*;
+
+use crate::{
+    checker, editor,
+    editor::SourceRange,
+    fixer::{self, SCRIPT_VERSION},
+};
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_unknown_function_suggestion() {
+    let code = "#:NavigateToUrll(\"https://a.b\");\n";
+    let diagnostics = checker::check_program(code);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "Function 'NavigateToUrll' doesn't exist! Did you mean 'NavigateToUrl'?"
+    );
+    assert_eq!((diagnostics[0].start, diagnostics[0].end), (2, 16));
+
+    let fix = &diagnostics[0].fixes[0];
+    assert_eq!(fix.title, "Change to 'NavigateToUrl'");
+    assert_eq!((fix.start, fix.end), (2, 16));
+    assert_eq!(fix.replacement, "NavigateToUrl");
+    assert!(!fix.preferred);
+
+    // Suggestions are names the checker accepts, not display names
+    let diagnostics = checker::check_program("#:ExtUIGetGroupFromLabl(\"x\");\n");
+    assert_eq!(
+        diagnostics[0].message,
+        "Function 'ExtUIGetGroupFromLabl' doesn't exist! Did you mean 'ExtUIGetGroupFromLabel'?"
+    );
+    assert_eq!(
+        diagnostics[0].fixes[0].replacement,
+        "ExtUIGetGroupFromLabel"
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_unknown_variable_suggestion() {
+    let diagnostics =
+        checker::check_program("var username = \"sysadmin\";\n#:NavigateToUrl(usrname);\n");
+
+    assert_eq!(
+        diagnostics[0].message,
+        "Variable \"usrname\" doesn't exist! Did you mean 'username'?"
+    );
+    assert_eq!(diagnostics[0].line, 2);
+    assert_eq!(diagnostics[0].fixes[0].replacement, "username");
+
+    // Nothing close enough to suggest
+    let diagnostics = checker::check_program("#:NavigateToUrl(address);\n");
+    assert_eq!(
+        diagnostics[0].message,
+        "Variable \"address\" doesn't exist!"
+    );
+    assert!(diagnostics[0].fixes.is_empty());
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_unknown_package_suggestion() {
+    let code =
+        "#:Assert(true, bultin:FailureHandling.Stop);\nbuiltn:NavigateToUrl(\"https://a.b\");\n";
+    let diagnostics = checker::check_program(code);
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(
+        diagnostics[0].message,
+        "Package 'bultin' doesn't exist! Did you mean 'builtin'?"
+    );
+    assert_eq!(&code[diagnostics[0].start..diagnostics[0].end], "bultin");
+    assert_eq!(&code[diagnostics[1].start..diagnostics[1].end], "builtn");
+    assert_eq!(diagnostics[1].fixes[0].replacement, "builtin");
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_code_actions_of_range() -> color_eyre::eyre::Result<()> {
+    let code = "#:NavigateToUrll(\"https://a.b\");\n#:ClickElementByText(\"View\", \"*\");\n";
+
+    let actions = editor::code_actions(code, SourceRange { start: 4, end: 4 });
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].title, "Change to 'NavigateToUrl'");
+    assert_eq!(actions[0].range, SourceRange { start: 2, end: 16 });
+    assert!(!actions[0].preferred);
+
+    let second_line = code.find("#:Click").unwrap();
+    let actions = editor::code_actions(
+        code,
+        SourceRange {
+            start: second_line,
+            end: code.len(),
+        },
+    );
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].title, "Replace with 'ClickElementByString'");
+    assert!(actions[0].preferred);
+
+    // Guesses are left to the editor
+    let fixed = fixer::fix_program(code, "view.atln")?;
+    assert!(fixed.code.starts_with(&format!(
+        "#[version={SCRIPT_VERSION}]\n#:NavigateToUrll(\"https://a.b\");\n"
+    )));
+
+    Ok(())
+}
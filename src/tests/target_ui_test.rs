@@ -0,0 +1,55 @@
+use wasm_bindgen_test::*;
+
+use crate::builtin_package_definition::{equivalent_function, TargetUI};
+use crate::checker;
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_equivalent_function() {
+    assert_eq!(
+        equivalent_function("ExtUIInputDateByLabelExact", TargetUI::Ext, TargetUI::MUI),
+        Some("MUIInputDateByLabelExact")
+    );
+    assert_eq!(
+        equivalent_function("MUIInputTimeByLabelExact", TargetUI::MUI, TargetUI::Ext),
+        None
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_call_of_other_ui() {
+    let diagnostics = checker::check_program(
+        "#[ui=mui]\n#:NavigateToUrl(\"https://a.b\");\n#:ExtUIInputDateByLabelExact(\"Date\", \"2024-01-01\");\n#:ExtUIInputNumberTextboxByLabelExact(\"Amount\", 5);\n",
+    );
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(
+        diagnostics[0].message,
+        "\"ExtUIInputDateByLabelExact\" is made for Ext, but the script declares #[ui=mui]. Did you mean 'MUIInputDateByLabelExact'?"
+    );
+    assert_eq!((diagnostics[0].line, diagnostics[0].column), (3, 3));
+    assert!(diagnostics[1]
+        .message
+        .ends_with("It has no MUI equivalent."));
+
+    assert!(crate::transpile_groovy(
+        "#[ui=mui]\n#:ExtUIInputDateByLabelExact(\"Date\", \"2024-01-01\");"
+    )
+    .is_err());
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_call_of_declared_ui() {
+    assert!(checker::check_program(
+        "#[ui=Ext]\n#:NavigateToUrl(\"https://a.b\");\n#:ExtUIInputDateByLabelExact(\"Date\", \"2024-01-01\");\n"
+    )
+    .is_empty());
+
+    let unknown = checker::check_program("#[ui=wpf]\n");
+    assert_eq!(
+        unknown[0].message,
+        "Unknown UI framework 'wpf', expected 'ext', 'mui' or 'any'"
+    );
+}
@@ -0,0 +1,55 @@
+use wasm_bindgen_test::*;
+
+use crate::ast;
+use crate::autalonparser::{AutalonParser, Rule};
+use crate::transpiler::{self, TranspileOptions, TranspilerOption};
+use crate::trivia::{self, CommentKind};
+use pest::Parser;
+
+const SCRIPT: &str =
+    "/* header */\n// Open the app\n#:NavigateToUrl(\"https://a.b\"); // go\nvar x = 1 /* one */ + 2;\n// bye\n";
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_annotate_attaches_comments() -> color_eyre::eyre::Result<()> {
+    let parsed = AutalonParser::parse(Rule::program, SCRIPT)?;
+    let program = trivia::annotate(SCRIPT, parsed);
+
+    let first = &program.statements[0].trivia;
+    assert_eq!(
+        first
+            .leading
+            .iter()
+            .map(|x| (x.kind, x.content.as_str()))
+            .collect::<Vec<(CommentKind, &str)>>(),
+        vec![
+            (CommentKind::Block, " header "),
+            (CommentKind::Line, " Open the app")
+        ]
+    );
+    assert_eq!(first.trailing[0].content, " go");
+
+    assert_eq!(program.statements[1].trivia.leading[0].content, " one ");
+    assert_eq!(program.dangling[0].content, " bye");
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_transpile_emits_comments_and_header() -> color_eyre::eyre::Result<()> {
+    let program = ast::parse_program(SCRIPT)?;
+    let options = TranspileOptions {
+        file_name: Some("login.atln".to_string()),
+        ..Default::default()
+    };
+
+    let script = transpiler::program_handler(TranspilerOption::Groovy, &program, &options)?.code;
+
+    assert!(script.starts_with("// Transpiled from login.atln by autalon-transpiler "));
+    assert!(script.contains(
+        "/* header */\n// Open the app\ndriver.navigate().to(\"https://a.b\"); // go\n/* one */\ndef x = 1 + 2;\n// bye\n"
+    ));
+
+    Ok(())
+}
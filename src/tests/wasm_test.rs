@@ -1,18 +1,20 @@
+use strum::IntoEnumIterator;
 use wasm_bindgen_test::*;
 
 use crate::autalonparser::{AutalonParser, Rule};
-use crate::checker;
+use crate::builtin_package_definition::{get_fn_metadata, BuiltinPkgFunctions};
+use crate::{ast, checker};
 use pest::Parser;
 
 #[test]
 #[wasm_bindgen_test]
 fn pass_returntype_comparation() -> color_eyre::eyre::Result<()> {
-    let mut test = AutalonParser::parse(Rule::comp_op, "true == true")?;
+    let mut test = AutalonParser::parse(Rule::expr, "true == true")?;
     let mut checker = checker::Checker::new();
 
     println!(
         "{}",
-        checker.get_comp_returntype(test.next().unwrap().into_inner())?
+        checker.get_expr_returntype(&mut ast::build_expr(test.next().unwrap())?)?
     );
     Ok(())
 }
@@ -20,12 +22,12 @@ fn pass_returntype_comparation() -> color_eyre::eyre::Result<()> {
 #[test]
 #[wasm_bindgen_test]
 fn pass_returntype_comparationlogic() -> color_eyre::eyre::Result<()> {
-    let mut test = AutalonParser::parse(Rule::logic_op, "true && false == false && false")?;
+    let mut test = AutalonParser::parse(Rule::expr, "true && false == false && false")?;
     let mut checker = checker::Checker::new();
 
     println!(
         "{}",
-        checker.get_logic_returntype(test.next().unwrap().into_inner())?
+        checker.get_expr_returntype(&mut ast::build_expr(test.next().unwrap())?)?
     );
     Ok(())
 }
@@ -33,12 +35,12 @@ fn pass_returntype_comparationlogic() -> color_eyre::eyre::Result<()> {
 #[test]
 #[wasm_bindgen_test]
 fn pass_returntype_logic() -> color_eyre::eyre::Result<()> {
-    let mut test = AutalonParser::parse(Rule::logic_op, "true && false")?;
+    let mut test = AutalonParser::parse(Rule::expr, "true && false")?;
     let mut checker = checker::Checker::new();
 
     println!(
         "{}",
-        checker.get_logic_returntype(test.next().unwrap().into_inner())?
+        checker.get_expr_returntype(&mut ast::build_expr(test.next().unwrap())?)?
     );
     Ok(())
 }
@@ -51,7 +53,7 @@ fn pass_returntype_expr_pkgmember_noargs() -> color_eyre::eyre::Result<()> {
 
     println!(
         "{}",
-        checker.get_expr_returntype(test.next().unwrap().into_inner())?
+        checker.get_expr_returntype(&mut ast::build_expr(test.next().unwrap())?)?
     );
     Ok(())
 }
@@ -64,24 +66,249 @@ fn pass_returntype_expr_pkgmember_args() -> color_eyre::eyre::Result<()> {
 
     println!(
         "{}",
-        checker.get_expr_returntype(test.next().unwrap().into_inner())?
+        checker.get_expr_returntype(&mut ast::build_expr(test.next().unwrap())?)?
     );
     Ok(())
 }
 
 #[test]
 #[wasm_bindgen_test]
-fn pass_vardeclaration() -> color_eyre::eyre::Result<()> {
-    let mut test = AutalonParser::parse(Rule::statement, "var test = \"asd\";")?;
-    checker::statement_checker(test.next().unwrap())?;
+fn pass_vardeclaration() {
+    assert!(checker::check_program("var test = \"asd\";")
+        .iter()
+        .all(|x| !x.is_error()));
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_varassignment() {
+    assert!(
+        checker::check_program("var test = \"asd\"; test = \"testassign\";")
+            .iter()
+            .all(|x| !x.is_error())
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_returntype_expr_enum_member() -> color_eyre::eyre::Result<()> {
+    let mut test = AutalonParser::parse(
+        Rule::expr,
+        "#:ClickElementByString(\"btnLogin\", ByOption.ID, \"*\", true);",
+    )?;
+    let mut checker = checker::Checker::new();
+
+    assert_eq!(
+        checker.get_expr_returntype(&mut ast::build_expr(test.next().unwrap())?)?,
+        "void"
+    );
     Ok(())
 }
 
 #[test]
 #[wasm_bindgen_test]
-fn pass_varassignment() -> color_eyre::eyre::Result<()> {
-    let mut test =
-        AutalonParser::parse(Rule::statement, "var test = \"asd\"; test = \"testassign\"")?;
-    checker::statement_checker(test.next().unwrap())?;
+fn fail_returntype_expr_unknown_enum_member() -> color_eyre::eyre::Result<()> {
+    let mut test = AutalonParser::parse(
+        Rule::expr,
+        "#:ClickElementByString(\"btnLogin\", ByOption.Idd, \"*\", true);",
+    )?;
+    let mut checker = checker::Checker::new();
+
+    let err = checker
+        .get_expr_returntype(&mut ast::build_expr(test.next().unwrap())?)
+        .unwrap_err();
+    assert!(err.to_string().contains("Did you mean 'ID'?"));
     Ok(())
 }
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_returntype_element_method_chain() -> color_eyre::eyre::Result<()> {
+    let mut test = AutalonParser::parse(
+        Rule::expr,
+        "#:GetElementByString(\"btnLogin\", ByOption.ID, \"*\", true).getText()",
+    )?;
+    let mut checker = checker::Checker::new();
+
+    assert_eq!(
+        checker.get_expr_returntype(&mut ast::build_expr(test.next().unwrap())?)?,
+        "string"
+    );
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_returntype_element_variable_method() -> color_eyre::eyre::Result<()> {
+    let mut declaration = AutalonParser::parse(
+        Rule::statement,
+        "var el = #:ExtUIGetInputFromLabel(\"Name\");",
+    )?;
+    let mut call = AutalonParser::parse(Rule::expr, "el.sendText(\"asd\")")?;
+    let mut checker = checker::Checker::new();
+
+    checker.check_statement(&mut ast::build_statement(
+        declaration.next().unwrap(),
+        Default::default(),
+    )?)?;
+    assert_eq!(
+        checker.get_expr_returntype(&mut ast::build_expr(call.next().unwrap())?)?,
+        "void"
+    );
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_returntype_assertion() -> color_eyre::eyre::Result<()> {
+    let mut test = AutalonParser::parse(
+        Rule::expr,
+        "#:VerifyElementText(#:ExtUIGetInputFromLabel(\"Name\"), \"Bob\", FailureHandling.Continue)",
+    )?;
+    let mut checker = checker::Checker::new();
+
+    assert_eq!(
+        checker.get_expr_returntype(&mut ast::build_expr(test.next().unwrap())?)?,
+        "void"
+    );
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_assertion_default_failure_handling() -> color_eyre::eyre::Result<()> {
+    // The failure handling is optional, and stops the test by default
+    let code = "var el = #:GetElementByString(\"lblName\", ByOption.ID, \"*\", true);\n#:Assert(true);\n#:VerifyElementText(el, \"Bob\");\n#:VerifyUrlContains(\"/home\", FailureHandling.Continue);\n";
+    assert!(checker::check_program(code).is_empty());
+
+    let script = crate::transpile_groovy(code)?;
+    assert!(script.contains("WebUI.verifyEqual(true, true, FailureHandling.STOP_ON_FAILURE);"));
+    assert!(script.contains(
+        "WebUI.verifyMatch(el.getText(), \"Bob\", false, FailureHandling.STOP_ON_FAILURE);"
+    ));
+    assert!(script.contains("FailureHandling.CONTINUE_ON_FAILURE);"));
+
+    // Only the optional arguments can be left out
+    assert!(!checker::check_program("#:VerifyElementText(\"Bob\");\n").is_empty());
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_every_builtin_checks_and_transpiles() -> color_eyre::eyre::Result<()> {
+    for builtin_fn in BuiltinPkgFunctions::iter() {
+        let params = get_fn_metadata(&builtin_fn).args;
+        let args = params
+            .iter()
+            .map(|x| match x.arg_type {
+                "string" => "\"a\"",
+                "number" => "1",
+                "decimal" => "1.5",
+                "bool" => "true",
+                "byoption" => "ByOption.ID",
+                "failurehandling" => "FailureHandling.Stop",
+                "element" => "el",
+                arg_type => panic!("No sample value for {arg_type}"),
+            })
+            .collect::<Vec<&str>>();
+
+        let mut code = format!("#:{builtin_fn}({});\n", args.join(", "));
+        if params.iter().any(|x| x.arg_type == "element") {
+            code.insert_str(
+                0,
+                "var el = #:GetElementByString(\"a\", ByOption.ID, \"*\", true);\n",
+            );
+        }
+
+        assert!(checker::check_program(&code).is_empty(), "{code}");
+        crate::transpile_groovy(&code).map_err(|x| x.wrap_err(code))?;
+    }
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_returntype_assertion_non_bool() -> color_eyre::eyre::Result<()> {
+    let mut test = AutalonParser::parse(Rule::expr, "#:Assert(\"yes\", FailureHandling.Stop)")?;
+    let mut checker = checker::Checker::new();
+
+    assert!(checker
+        .get_expr_returntype(&mut ast::build_expr(test.next().unwrap())?)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_check_program_reports_every_statement() {
+    let diagnostics =
+        checker::check_program("#:NavigateToUrl(1);\n#:NavigateToUrl(\"a\");\n#:NavigateToUrl(2);");
+
+    assert_eq!(
+        diagnostics.iter().map(|x| x.line).collect::<Vec<usize>>(),
+        vec![1, 3]
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_check_program_warns_constant_arithmetic() {
+    let diagnostics = checker::check_program(
+        "var a = 2147483647 + 1;\nvar b = 10 / (2 - 2);\nvar c = 7 % 0;\nvar d = 2 ^ 10 * 1000;\nvar e = 2 ^ 31;\nvar f = 1.5 / 0.0;\nconst Z = 0;\nvar g = 1 / Z;\nvar h = 1.5 / 0.5;\n",
+    );
+
+    // `2 ^ 31` is widened by Groovy, so it isn't warned about
+    assert!(diagnostics.iter().all(|x| !x.is_error()));
+    assert_eq!(
+        diagnostics
+            .iter()
+            .filter(|x| x.code.is_none())
+            .map(|x| (x.line, x.message.as_str()))
+            .collect::<Vec<(usize, &str)>>(),
+        vec![
+            (1, "'2147483647 + 1' overflows a 32-bit integer"),
+            (2, "Division by zero"),
+            (3, "Division by zero"),
+            (6, "Division by zero"),
+            (8, "Division by zero"),
+        ]
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pass_returntype_decimal_promotion() -> color_eyre::eyre::Result<()> {
+    let mut checker = checker::Checker::new();
+
+    for (code, returntype) in [
+        ("12.50 + 1", "decimal"),
+        ("1e3 * 2", "decimal"),
+        ("-2.5E-3", "decimal"),
+        ("2 + 3", "number"),
+        ("1.5 < 2", "bool"),
+    ] {
+        let mut test = AutalonParser::parse(Rule::expr, code)?;
+        assert_eq!(
+            checker.get_expr_returntype(&mut ast::build_expr(test.next().unwrap())?)?,
+            returntype,
+            "{code}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn fail_decimal_narrowing() {
+    let diagnostics = checker::check_program(
+        "#:SetWindowDimension(12.5, 600);\n#:ExtUIInputNumberTextboxByLabelExact(\"Price\", 12);\n",
+    );
+
+    assert_eq!(
+        diagnostics.iter().map(|x| x.line).collect::<Vec<usize>>(),
+        vec![1]
+    );
+}
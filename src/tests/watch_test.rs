@@ -0,0 +1,74 @@
+use std::path::{Path, PathBuf};
+
+use crate::libs::{
+    katalon_project,
+    watch::{self, WatchEvent},
+};
+
+/// Empty directory of its own for a test, removed when dropped
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> TempDir {
+        let path = std::env::temp_dir().join(format!("autalon-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+
+        TempDir(path)
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn pass_sync_script_maps_and_removes_outputs() -> color_eyre::eyre::Result<()> {
+    let source = TempDir::new("watch-source");
+    let project = TempDir::new("watch-project");
+
+    let script = source.path().join("login").join("Admin Login.atln");
+    std::fs::create_dir_all(script.parent().unwrap())?;
+    std::fs::write(&script, "#:NavigateToUrl(\"https://a.b\");\n")?;
+
+    // Nested scripts keep their folder in the test case name
+    let script_path = project
+        .path()
+        .join(katalon_project::script_path("login/Admin Login"));
+    let test_case_path = project
+        .path()
+        .join(katalon_project::test_case_path("login/Admin Login"));
+
+    let event = watch::sync_script(source.path(), project.path(), &script)?;
+    assert!(matches!(event, WatchEvent::Transpiled { output, .. } if output == script_path));
+    assert!(std::fs::read_to_string(&script_path)?.contains("driver.navigate().to("));
+    assert!(test_case_path.exists());
+
+    // Files Katalon put next to the script aren't ours to remove
+    let script_dir = script_path.parent().unwrap();
+    let katalon_file = script_dir.join("Script.properties");
+    std::fs::write(&katalon_file, "")?;
+
+    std::fs::remove_file(&script)?;
+    let event = watch::sync_script(source.path(), project.path(), &script)?;
+    assert!(matches!(event, WatchEvent::Removed { .. }));
+    assert!(!script_path.exists());
+    assert!(!test_case_path.exists());
+    assert!(katalon_file.exists());
+
+    // The script directory goes once nothing else is left in it
+    std::fs::remove_file(&katalon_file)?;
+    std::fs::write(&script, "#:NavigateToUrl(\"https://a.b\");\n")?;
+    watch::sync_script(source.path(), project.path(), &script)?;
+    std::fs::remove_file(&script)?;
+    watch::sync_script(source.path(), project.path(), &script)?;
+    assert!(!script_dir.exists());
+
+    Ok(())
+}
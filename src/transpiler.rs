@@ -1,15 +1,43 @@
-use crate::autalonparser::Rule;
+use crate::ast::Program;
 use color_eyre::eyre::Report;
-use pest::iterators::Pair;
+use strum_macros::{Display, EnumIter, EnumString};
 
+pub mod escape;
 pub mod katalon_prealpha;
+pub mod source_map;
 
+use source_map::Transpiled;
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, EnumIter, Display)]
+#[strum(serialize_all = "lowercase")]
 pub enum TranspilerOption {
     Groovy,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct TranspileOptions {
+    /// Name of the script file, recorded in the header of the generated code
+    pub file_name: Option<String>,
+    /// Put a `// atln:N` comment after every statement, pointing at its line in the script
+    pub line_markers: bool,
+}
+
+impl TranspileOptions {
+    /// Text of the header comment put on top of the generated code
+    pub fn header(&self) -> String {
+        let version = format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+
+        match &self.file_name {
+            Some(file_name) => format!("Transpiled from {file_name} by {version}"),
+            None => format!("Transpiled by {version}"),
+        }
+    }
+}
+
 #[allow(unreachable_patterns)]
-fn get_transpiler(option: TranspilerOption) -> impl Fn(&[Pair<Rule>]) -> Result<String, Report> {
+fn get_transpiler(
+    option: TranspilerOption,
+) -> impl Fn(&Program, &TranspileOptions) -> Result<Transpiled, Report> {
     match option {
         TranspilerOption::Groovy => katalon_prealpha::program_handler,
         _ => unimplemented!(),
@@ -18,7 +46,8 @@ fn get_transpiler(option: TranspilerOption) -> impl Fn(&[Pair<Rule>]) -> Result<
 
 pub fn program_handler(
     transpiler: TranspilerOption,
-    pair: &[Pair<Rule>],
-) -> Result<String, Report> {
-    get_transpiler(transpiler)(pair)
+    program: &Program,
+    options: &TranspileOptions,
+) -> Result<Transpiled, Report> {
+    get_transpiler(transpiler)(program, options)
 }
@@ -0,0 +1,30 @@
+//! Turning string values into Groovy string literals, so that whatever the script holds stays
+//! literal text in the generated code.
+
+/// Content of a double quoted string literal holding exactly `value`
+pub fn escape(value: &str) -> String {
+    let mut res = String::new();
+
+    for c in value.chars() {
+        match c {
+            '\\' => res.push_str("\\\\"),
+            '"' => res.push_str("\\\""),
+            // `$` starts an interpolation in double quoted strings
+            '$' => res.push_str("\\$"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            '\u{8}' => res.push_str("\\b"),
+            '\u{c}' => res.push_str("\\f"),
+            c if c.is_control() => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+
+    res
+}
+
+/// `value` as a double quoted string literal
+pub fn quote(value: &str) -> String {
+    format!("\"{}\"", escape(value))
+}
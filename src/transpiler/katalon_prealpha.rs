@@ -1,271 +1,323 @@
 mod consts;
-mod pkgdef;
-
-use std::collections::HashMap;
-
-use crate::{autalonparser::Rule, checker::funcs::unwrap_inner};
-use color_eyre::eyre::{bail, ContextCompat, Report};
-use pest::iterators::Pair;
-
-#[tracing::instrument(skip_all)]
-pub fn program_handler(pair: &[Pair<Rule>]) -> Result<String, Report> {
-    let res = pair
-        .iter()
-        .map(|x| unwrap_inner(x.clone()))
-        .collect::<Result<Vec<Pair<Rule>>, Report>>()?
-        .into_iter()
-        .map(statement_handler)
-        .collect::<Result<Vec<String>, Report>>()?
-        .join("\n");
-
-    Ok(consts::add_prepend(&res))
-}
+pub(crate) mod pkgdef;
+
+use std::{collections::HashMap, str::FromStr};
+
+use crate::{
+    ast::{
+        self, Assoc, BinaryOp, Expr, ExprKind, Literal, OperatorTable, Program, Span,
+        StatementKind, StringPart, AUTALON_OPERATORS,
+    },
+    builtin_package_definition::{get_fn_metadata, BuiltinPkgFunctions, DriverVersion},
+    checker::{config::CheckerConfig, tree_checker},
+    trivia::{Comment, CommentKind},
+};
+use color_eyre::eyre::{bail, eyre, Report};
+
+use super::{
+    escape,
+    source_map::{LineMapping, SourceMap, Transpiled},
+    TranspileOptions,
+};
 
 #[tracing::instrument(skip_all)]
-pub fn statement_handler(pair: Pair<Rule>) -> Result<String, Report> {
-    Ok(match pair.as_rule() {
-        Rule::expr => expr_convert(pair)?,
-        Rule::var_declaration => var_declaration_convert(pair)?,
-        Rule::var_assignment => var_assignment_convert(pair)?,
-        Rule::escape_block => "".to_string(),
-        _ => unreachable!("{:?} is not implemented yet!", pair.as_rule()),
-    } + ";")
-}
+pub fn program_handler(
+    program: &Program,
+    options: &TranspileOptions,
+) -> Result<Transpiled, Report> {
+    let driver = CheckerConfig::from_program(program)?.driver;
+
+    // Generated lines, with the script line they come from
+    let mut lines: Vec<(String, Option<usize>)> = vec![];
+
+    for statement in &program.statements {
+        lines.extend(
+            statement
+                .trivia
+                .leading
+                .iter()
+                .map(|x| (comment_convert(x), None)),
+        );
+
+        let source_line = statement.span.line;
+        let mut converted = std::iter::once(statement_handler(&statement.kind, driver)?)
+            .chain(statement.trivia.trailing.iter().map(comment_convert))
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        if options.line_markers {
+            converted += &format!(" // atln:{source_line}");
+        }
 
-#[tracing::instrument(skip_all)]
-fn pair_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    match pair.as_rule() {
-        Rule::atomic_expression => pair_convert(unwrap_inner(pair)?),
-        Rule::basic_expr => pair_convert(unwrap_inner(pair)?),
-
-        Rule::comparable => comparable_convert(pair), // Ok("bool".to_string()),
-        Rule::string => str_convert(pair),            // Ok("string".to_string()),
-        Rule::number => number_convert(pair),         // Ok("number".to_string()),
-        Rule::bool => bool_convert(pair),             // Ok("bool".to_string()),
-        Rule::byoption_enum => byoption_convert(pair), // Ok("number".to_string()),
-
-        Rule::logic_op => logicop_convert(pair), // Ok("bool".to_string()),
-        Rule::comp_op => comp_op_convert(pair),  // Ok("bool".to_string()),
-
-        Rule::function_call => fn_convert(pair), // get_fn_pair_return_type(pair),
-        Rule::member_access => member_access_convert(pair), // get_member_return_type(pair),
-
-        Rule::eq_op => compop_symbol_convert(pair),
-        Rule::ne_op => compop_symbol_convert(pair),
-        Rule::lt_op => compop_symbol_convert(pair),
-        Rule::le_op => compop_symbol_convert(pair),
-        Rule::gt_op => compop_symbol_convert(pair),
-        Rule::ge_op => compop_symbol_convert(pair),
-
-        Rule::and_op => logicop_symbol_convert(pair),
-        Rule::or_op => logicop_symbol_convert(pair),
-
-        Rule::add_op => arithop_symbol_convert(pair),
-        Rule::sub_op => arithop_symbol_convert(pair),
-        Rule::div_op => arithop_symbol_convert(pair),
-        Rule::mul_op => arithop_symbol_convert(pair),
-        Rule::mod_op => arithop_symbol_convert(pair),
-        Rule::pow_op => arithop_symbol_convert(pair),
-
-        _ => unimplemented!(),
+        lines.extend(
+            converted
+                .lines()
+                .map(|x| (x.to_string(), Some(source_line))),
+        );
     }
+    lines.extend(program.dangling.iter().map(|x| (comment_convert(x), None)));
+
+    let prepend = consts::prepend(&format!("// {}", options.header()), driver);
+    let first_line = prepend.matches('\n').count() + 1;
+
+    Ok(Transpiled {
+        code: prepend
+            + &lines
+                .iter()
+                .map(|(x, _)| x.as_str())
+                .collect::<Vec<&str>>()
+                .join("\n")
+            + consts::APPEND,
+        source_map: SourceMap {
+            source_file: options.file_name.to_owned(),
+            mappings: lines
+                .iter()
+                .enumerate()
+                .filter_map(|(i, (_, source_line))| {
+                    source_line.map(|source_line| LineMapping {
+                        generated_line: first_line + i,
+                        source_line,
+                    })
+                })
+                .collect(),
+        },
+    })
 }
 
 #[tracing::instrument(skip_all)]
-fn expr_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    Ok(pair
-        .into_inner()
-        .into_iter()
-        .map(pair_convert)
-        .collect::<Result<Vec<String>, Report>>()?
-        .join(" "))
+fn comment_convert(comment: &Comment) -> String {
+    match comment.kind {
+        CommentKind::Line => format!("//{}", comment.content),
+        CommentKind::Block => format!("/*{}*/", comment.content),
+    }
 }
 
 #[tracing::instrument(skip_all)]
-fn fn_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    if pair.as_rule() != Rule::function_call {
-        bail!("Pair is not a function")
-    }
-
-    let mut inner_pair = pair.into_inner();
-
-    let member_acc = inner_pair.next().context("Can't get member or fn name")?;
-    let args = inner_pair.next(); // If none, no args
-
-    let fn_tokens = member_acc
-        .into_inner()
-        .into_iter()
-        .collect::<Vec<Pair<Rule>>>();
-
-    let (pkg_name, val_name, fn_name) = match fn_tokens.len() {
-        3 => (
-            Some(unwrap_inner(fn_tokens[0].clone())?),
-            Some(&fn_tokens[1]),
-            &fn_tokens[2],
-        ),
-        2 => {
-            if fn_tokens[0].as_rule() == Rule::package {
-                (
-                    Some(unwrap_inner(fn_tokens[0].clone())?),
-                    None,
-                    &fn_tokens[1],
-                )
-            } else {
-                (None, Some(&fn_tokens[0]), &fn_tokens[1])
-            }
+pub fn statement_handler(
+    statement: &StatementKind,
+    driver: DriverVersion,
+) -> Result<String, Report> {
+    Ok(match statement {
+        StatementKind::Expr(expr) => expr_convert(expr, driver)?,
+        StatementKind::VarDeclaration {
+            name,
+            value,
+            constant,
+        } => {
+            let keyword = if *constant { "final" } else { "def" };
+            format!("{keyword} {} = {}", name.name, expr_convert(value, driver)?)
         }
-        1 => (None, None, &fn_tokens[0]),
-        _ => bail!(
-            "Invalid function token length! Expected token count: 3; Received token count: {}",
-            fn_tokens.len()
-        ),
-    };
-
-    let converted_args = match args {
-        None => vec![],
-        Some(outer_pair) => outer_pair
-            .into_inner()
-            .into_iter()
-            .map(expr_convert)
-            .collect::<Result<Vec<String>, Report>>()?,
-    };
-
-    // Map arg values to [("{arg1}", argvalue), ("{arg2}", argvalue), ..., ("{argN}", argvalue)]
-    // This is for the formatter that accepts {argN}
-    let converted_args = converted_args
-        .iter()
-        .enumerate()
-        .map(|(i, x)| (format!("arg{}", i + 1), x.to_owned()))
-        .collect::<HashMap<String, String>>();
-
-    let res = match pkg_name {
-        Some(pkg) => {
-            // There's package name. check based on that
-
-            match val_name {
-                Some(_) => unimplemented!(), // No builtin value unfortunately for now, so skipping it.
-                None => self::pkgdef::get_default_fn_template(fn_name.as_str(), pkg.as_str())?,
-            }
+        StatementKind::VarAssignment { name, value } => {
+            format!("{} = {}", name.name, expr_convert(value, driver)?)
         }
-        None => unimplemented!(), // Fn is local, none of this one for now
-    };
-
-    let formatted_res = if !converted_args.is_empty() {
-        match strfmt::strfmt(res, &converted_args) {
-            Ok(fmtstr) => fmtstr,
-            Err(e) => panic!("{}", e),
-        }
-    } else {
-        res.to_string()
-    };
+        StatementKind::Escape(_) => "".to_string(),
+    } + ";")
+}
 
-    Ok(formatted_res)
+/// `^` is XOR in Groovy, powers are written `**`
+fn binary_symbol(op: BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Pow => "**",
+        op => op.symbol(),
+    }
 }
 
-#[tracing::instrument(skip_all)]
-fn member_access_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    // For now, just return what's inside the cast
-    let inner = unwrap_inner(pair)?;
-    Ok(inner.as_str().to_string())
+/// Groovy binds its operators like Autalon, except that its power operator is left associative
+const GROOVY_OPERATORS: OperatorTable = OperatorTable {
+    binary: |op| match op {
+        BinaryOp::Pow => (8, Assoc::Left),
+        op => (AUTALON_OPERATORS.binary)(op),
+    },
+    unary: AUTALON_OPERATORS.unary,
+};
+
+/// Convert an operand, in parentheses when Groovy would otherwise group it differently
+fn operand_convert(
+    parent: &ExprKind,
+    operand: &Expr,
+    side: Assoc,
+    driver: DriverVersion,
+) -> Result<String, Report> {
+    let converted = expr_convert(operand, driver)?;
+
+    if GROOVY_OPERATORS.needs_parens(parent, operand, side) {
+        return Ok(format!("({converted})"));
+    }
+
+    Ok(converted)
 }
 
-#[tracing::instrument(skip_all)]
-fn logicop_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    let mut inner_pair = pair.into_inner();
-
-    let lhs = inner_pair
-        .next()
-        .context("Can't get left hand side from pair")?;
-    let logic_op = inner_pair.next().context("Can't get operator from pair")?;
-    let rhs = inner_pair
-        .next()
-        .context("Can't get right hand side from pair")?;
-
-    let lhs_conv = pair_convert(lhs)?;
-    let op_conv = pair_convert(logic_op)?;
-    let rhs_conv = pair_convert(rhs)?;
-
-    Ok(format!("{} {} {}", lhs_conv, op_conv, rhs_conv))
+fn concat_operand_convert(
+    parent: &ExprKind,
+    operand: &Expr,
+    side: Assoc,
+    driver: DriverVersion,
+) -> Result<String, Report> {
+    match operand.ty {
+        Some("string") => operand_convert(parent, operand, side, driver),
+        _ => fill_template(
+            self::pkgdef::get_default_method_template("toString", receiver_type(operand)?)?,
+            Some(receiver_convert(operand, driver)?),
+            vec![],
+        ),
+    }
 }
 
-#[tracing::instrument(skip_all)]
-fn comp_op_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    let mut inner_pair = pair.into_inner();
-
-    let lhs = inner_pair
-        .next()
-        .context("Can't get left hand side from pair")?;
-    let comp_op = inner_pair.next().context("Can't get operator from pair")?;
-    let rhs = inner_pair
-        .next()
-        .context("Can't get right hand side from pair")?;
-
-    let lhs_conv = pair_convert(lhs)?;
-    let op_conv = pair_convert(comp_op)?;
-    let rhs_conv = pair_convert(rhs)?;
-
-    Ok(format!("{} {} {}", lhs_conv, op_conv, rhs_conv))
+/// Type the checker found for the value a method is called on, which picks the method template
+fn receiver_type(receiver: &Expr) -> Result<&'static str, Report> {
+    receiver
+        .ty
+        .ok_or_else(|| eyre!("Type of the method receiver is unknown, check the script first"))
 }
 
-#[tracing::instrument(skip_all)]
-fn var_declaration_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    let var_assignment = unwrap_inner(pair)?;
+/// Operators bind looser than the method templates around them
+fn receiver_convert(receiver: &Expr, driver: DriverVersion) -> Result<String, Report> {
+    let converted = expr_convert(receiver, driver)?;
 
-    let var_assignment_conv = var_assignment_convert(var_assignment)?;
+    if matches!(
+        receiver.kind,
+        ExprKind::Binary { .. } | ExprKind::Unary { .. }
+    ) {
+        return Ok(format!("({converted})"));
+    }
 
-    Ok(format!("def {}", var_assignment_conv))
+    Ok(converted)
 }
 
 #[tracing::instrument(skip_all)]
-fn var_assignment_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    let mut inner = pair.into_inner();
-
-    let identifier = inner.next().context("Can't get variable identity")?;
-    let expr = inner.next().context("Can't get variable expression")?;
+fn expr_convert(expr: &Expr, driver: DriverVersion) -> Result<String, Report> {
+    match &expr.kind {
+        ExprKind::Literal(Literal::String(value)) => Ok(escape::quote(&ast::unescape(value))),
+        // Only the expressions become GString placeholders, the text stays literal
+        ExprKind::Interpolation(parts) => Ok(format!(
+            "\"{}\"",
+            parts
+                .iter()
+                .map(|part| match part {
+                    StringPart::Text(text) => Ok(escape::escape(&ast::unescape(text))),
+                    StringPart::Expr(expr) => Ok(format!("${{{}}}", expr_convert(expr, driver)?)),
+                })
+                .collect::<Result<String, Report>>()?
+        )),
+        ExprKind::Literal(Literal::Number(value)) => Ok(value.to_owned()),
+        // BigDecimal keeps the digits exactly as written
+        ExprKind::Literal(Literal::Decimal(value)) => Ok(format!("{}G", value.replace('E', "e"))),
+        ExprKind::Literal(Literal::Bool(value)) => Ok(value.to_string()),
+
+        // For now, variables are just returned as is
+        ExprKind::Variable(identifier) => Ok(identifier.name.to_owned()),
+        ExprKind::EnumMember {
+            package,
+            name,
+            member,
+        } => {
+            let pkg = package.as_deref().unwrap_or("builtin");
+            let template = self::pkgdef::get_default_enum_template(&name.name, &member.name, pkg)?;
+
+            let member = HashMap::from([("member".to_string(), member.name.as_str())]);
+            Ok(strfmt::strfmt(template, &member)?)
+        }
 
-    let identifier_str = identifier.as_str();
-    let expr_parsed = expr_convert(expr)?;
+        // Deprecated aliases are called through the builtin they resolve to
+        ExprKind::Call {
+            package,
+            name,
+            args,
+            builtin,
+        } => match package {
+            Some(pkg) => {
+                let name = builtin.unwrap_or(&name.name);
+
+                fill_template(
+                    self::pkgdef::get_default_fn_template(name, pkg, driver)?,
+                    None,
+                    args_convert(&with_optional_args(name, args, expr.span)?, driver)?,
+                )
+            }
+            None => bail!("Local function '{}' is not supported yet", name.name),
+        },
+        ExprKind::MethodCall {
+            receiver,
+            name,
+            args,
+        } => fill_template(
+            self::pkgdef::get_default_method_template(&name.name, receiver_type(receiver)?)?,
+            Some(receiver_convert(receiver, driver)?),
+            args_convert(args, driver)?,
+        ),
 
-    Ok(format!("{} = {}", identifier_str, expr_parsed))
+        ExprKind::Unary { op, operand } => Ok(format!(
+            "{op}{}",
+            operand_convert(&expr.kind, operand, Assoc::Right, driver)?
+        )),
+        // Values added to a string are converted explicitly
+        ExprKind::Binary {
+            op: BinaryOp::Add,
+            lhs,
+            rhs,
+        } if expr.ty == Some("string") => Ok(format!(
+            "{} + {}",
+            concat_operand_convert(&expr.kind, lhs, Assoc::Left, driver)?,
+            concat_operand_convert(&expr.kind, rhs, Assoc::Right, driver)?
+        )),
+        ExprKind::Binary { op, lhs, rhs } => Ok(format!(
+            "{} {} {}",
+            operand_convert(&expr.kind, lhs, Assoc::Left, driver)?,
+            binary_symbol(*op),
+            operand_convert(&expr.kind, rhs, Assoc::Right, driver)?
+        )),
+        ExprKind::Index { target, index } => Ok(format!(
+            "{}[{}]",
+            expr_convert(target, driver)?,
+            expr_convert(index, driver)?
+        )),
+    }
 }
 
-#[tracing::instrument(skip_all)]
-fn comparable_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    let inner = unwrap_inner(pair)?;
-    Ok(inner.as_str().to_string())
-}
+/// Arguments of a builtin call, with the optional ones left out given their default value
+fn with_optional_args(name: &str, args: &[Expr], span: Span) -> Result<Vec<Expr>, Report> {
+    let mut args = args.to_vec();
 
-#[tracing::instrument(skip_all)]
-fn compop_symbol_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    Ok(pair.as_str().to_string())
-}
+    if let Ok(builtin_fn) = BuiltinPkgFunctions::from_str(name) {
+        for param in get_fn_metadata(&builtin_fn).args.iter().skip(args.len()) {
+            if param.optional {
+                args.push(tree_checker::default_arg(param, span)?);
+            }
+        }
+    }
 
-#[tracing::instrument(skip_all)]
-fn logicop_symbol_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    Ok(pair.as_str().to_string())
+    Ok(args)
 }
 
 #[tracing::instrument(skip_all)]
-fn arithop_symbol_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    Ok(pair.as_str().to_string())
+fn args_convert(args: &[Expr], driver: DriverVersion) -> Result<Vec<String>, Report> {
+    args.iter()
+        .map(|x| expr_convert(x, driver))
+        .collect::<Result<Vec<String>, Report>>()
 }
 
 #[tracing::instrument(skip_all)]
-fn str_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    Ok(pair.as_str().to_string())
-}
+fn fill_template(
+    template: &str,
+    self_value: Option<String>,
+    args: Vec<String>,
+) -> Result<String, Report> {
+    // Map arg values to [("{arg1}", argvalue), ("{arg2}", argvalue), ..., ("{argN}", argvalue)]
+    // This is for the formatter that accepts {argN}
+    let mut template_values = args
+        .into_iter()
+        .enumerate()
+        .map(|(i, x)| (format!("arg{}", i + 1), x))
+        .collect::<HashMap<String, String>>();
 
-#[tracing::instrument(skip_all)]
-fn number_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    Ok(pair.as_str().to_string())
-}
+    // Methods refer to the value they're called on as {self}
+    if let Some(self_value) = self_value {
+        template_values.insert("self".to_string(), self_value);
+    }
 
-#[tracing::instrument(skip_all)]
-fn bool_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    Ok(pair.as_str().to_string())
-}
+    if template_values.is_empty() {
+        return Ok(template.to_string());
+    }
 
-#[tracing::instrument(skip_all)]
-fn byoption_convert(pair: Pair<Rule>) -> Result<String, Report> {
-    Ok(pair.as_str().to_string())
+    Ok(strfmt::strfmt(template, &template_values)?)
 }
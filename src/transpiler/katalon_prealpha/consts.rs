@@ -1,4 +1,11 @@
-pub fn add_prepend(script: &str) -> String {
+use crate::builtin_package_definition::DriverVersion;
+
+pub const APPEND: &str = r#"
+
+}"#;
+
+/// Everything before the converted statements, starting with the header comment
+pub fn prepend(header: &str, driver: DriverVersion) -> String {
     const PREPEND: &str = r#"import static com.kms.katalon.core.checkpoint.CheckpointFactory.findCheckpoint
 import static com.kms.katalon.core.testcase.TestCaseFactory.findTestCase
 import static com.kms.katalon.core.testdata.TestDataFactory.findTestData
@@ -32,13 +39,13 @@ use (ExtensionsKt) {
 
 def getElExt = driver.getElement()
 def extUIGetter = driver.uiGetter().extUI()
-def reactMUIGetter = driver.uiGetter().reactMUI()
-
 "#;
 
-    const APPEND: &str = r#"
-
-}"#;
+    // The React MUI getter came with driver 0.2.0
+    let getters = match driver {
+        DriverVersion::V0_1_1 => "",
+        DriverVersion::V0_2_0 => "def reactMUIGetter = driver.uiGetter().reactMUI()\n",
+    };
 
-    String::from(PREPEND) + script + APPEND
+    format!("{header}\n{PREPEND}{getters}\n")
 }
@@ -1,100 +1,142 @@
 use color_eyre::eyre::{bail, Report};
 use std::str::FromStr;
 
-use crate::builtin_package_definition::{self, BuiltinPkgFunctions};
+use crate::builtin_package_definition::{
+    self, BuiltinPkgEnums, BuiltinPkgFunctions, ConversionMethods, DriverVersion, ElementMethods,
+};
 
-fn get_katalon_default_fn_metadata<'a>(builtin_fn: BuiltinPkgFunctions) -> &'a str {
-    match builtin_fn {
-        BuiltinPkgFunctions::NavigateToUrl => r#"driver.navigate().to({arg1})"#,
-        BuiltinPkgFunctions::GetElementByString => {
+fn get_katalon_default_fn_metadata<'a>(
+    builtin_fn: BuiltinPkgFunctions,
+    driver: DriverVersion,
+) -> Result<&'a str, Report> {
+    Ok(match (&builtin_fn, driver) {
+        (BuiltinPkgFunctions::NavigateToUrl, _) => r#"driver.navigate().to({arg1})"#,
+        // Elements can't be waited for before 0.2.0
+        (BuiltinPkgFunctions::GetElementByString, DriverVersion::V0_1_1) => {
+            r#"driver.getElement().byString({arg1}, {arg2}, {arg3}, {arg4})"#
+        }
+        (BuiltinPkgFunctions::GetElementByString, DriverVersion::V0_2_0) => {
             r#"driver.getElement().byString({arg1}, {arg2}, {arg3}, {arg4}).untilElementInteractable()"#
         }
-        BuiltinPkgFunctions::ClickElementByString => {
+        (BuiltinPkgFunctions::ClickElementByString, DriverVersion::V0_1_1) => {
+            r#"driver.getElement().byString({arg1}, {arg2}, {arg3}, {arg4}).click()"#
+        }
+        (BuiltinPkgFunctions::ClickElementByString, DriverVersion::V0_2_0) => {
             r#"driver.getElement().byString({arg1}, {arg2}, {arg3}, {arg4}).untilElementInteractable().click()"#
         }
-        BuiltinPkgFunctions::SendTextToElementByString => {
+        (BuiltinPkgFunctions::SendTextToElementByString, DriverVersion::V0_1_1) => {
+            r#"driver.getElement().byString({arg1}, {arg3}, {arg4}, {arg5}).sendKeys({arg2})"#
+        }
+        (BuiltinPkgFunctions::SendTextToElementByString, DriverVersion::V0_2_0) => {
             r#"driver.getElement().byString({arg1}, {arg3}, {arg4}, {arg5}).untilElementInteractable().sendKeys({arg2})"#
         }
-        BuiltinPkgFunctions::ExtUIGetInputFromLabel => {
+        (BuiltinPkgFunctions::ExtUIGetInputFromLabel, _) => {
             r#"extUIGetter.getInputFromLabel({arg1})"#
         }
-        BuiltinPkgFunctions::ExtUIGetIFrameFromLabel => {
+        (BuiltinPkgFunctions::ExtUIGetIFrameFromLabel, _) => {
             r#"extUIGetter.getIFrameFromLabel({arg1})"#
         }
-        BuiltinPkgFunctions::ExtUIGetWindowFromLabel => {
+        (BuiltinPkgFunctions::ExtUIGetWindowFromLabel, _) => {
             r#"extUIGetter.getWindowFromTitle({arg1})"#
         }
-        BuiltinPkgFunctions::ExtUIGetGroupFromLabel => {
+        (BuiltinPkgFunctions::ExtUIGetGroupFromLabel, _) => {
             r#"extUIGetter.getGroupFromTitle({arg1})"#
         }
-        BuiltinPkgFunctions::ExtUIInputDateByLabelExact => {
+        (BuiltinPkgFunctions::ExtUIInputDateByLabelExact, _) => {
             r#"extUIGetter.getInputFromLabel({arg1}).shouldBe().date().sendText({arg2}, false)"#
         }
-        BuiltinPkgFunctions::ExtUIInputHtmlByLabelExact => {
+        (BuiltinPkgFunctions::ExtUIInputHtmlByLabelExact, _) => {
             r#"extUIGetter.getIFrameFromLabel({arg1}).shouldBe().htmlEditor().sendText({arg2})"#
         }
-        BuiltinPkgFunctions::ExtUIInputNumberTextboxByLabelExact => {
-            r#"extUIGetter.getInputFromLabel({arg1}).shouldBe().numberTextbox().sendText({arg2})"#
+        (BuiltinPkgFunctions::ExtUIInputNumberTextboxByLabelExact, _) => {
+            r#"extUIGetter.getInputFromLabel({arg1}).shouldBe().numberTextbox().sendText(({arg2} as BigDecimal).toPlainString())"#
         }
-        BuiltinPkgFunctions::ExtUIInputTextboxByLabelExact => {
+        (BuiltinPkgFunctions::ExtUIInputTextboxByLabelExact, _) => {
             r#"extUIGetter.getInputFromLabel({arg1}).shouldBe().textbox().sendText({arg2})"#
         }
-        BuiltinPkgFunctions::ExtUIInputDropdownUsingTextByLabelExact => {
+        (BuiltinPkgFunctions::ExtUIInputDropdownUsingTextByLabelExact, _) => {
             r#"extUIGetter.getInputFromLabel({arg1}).shouldBe().dropdown().selectElementFromText({arg2})"#
         }
-        BuiltinPkgFunctions::ExtUIInputDropdownUsingIndexByLabelExact => {
+        (BuiltinPkgFunctions::ExtUIInputDropdownUsingIndexByLabelExact, _) => {
             r#"extUIGetter.getInputFromLabel({arg1}).shouldBe().dropdown().selectElementOnIndex({arg2})"#
         }
-        BuiltinPkgFunctions::ExtUIInputRadioUsingTextByLabelExact => {
+        (BuiltinPkgFunctions::ExtUIInputRadioUsingTextByLabelExact, _) => {
             r#"extUIGetter.getInputFromLabel({arg1}).shouldBe().radio().selectElementFromText({arg2})"#
         }
-        BuiltinPkgFunctions::ExtUIInputRadioUsingIndexByLabelExact => {
+        (BuiltinPkgFunctions::ExtUIInputRadioUsingIndexByLabelExact, _) => {
             r#"extUIGetter.getInputFromLabel({arg1}).shouldBe().radio().selectElementOnIndex({arg2})"#
         }
-        BuiltinPkgFunctions::GetAndSwitchToAnyIFrame => {
+        (BuiltinPkgFunctions::GetAndSwitchToAnyIFrame, _) => {
             r#"driver = driver.waitUntilFrameLoads(By.xpath('//iframe')); driver = new Webdriverended(driver)"#
         }
-        BuiltinPkgFunctions::GetAndSwitchToParentIFrame => {
+        (BuiltinPkgFunctions::GetAndSwitchToParentIFrame, _) => {
             r#"driver = driver.switchTo().parentFrame(); driver = new Webdriverended(driver)"#
         }
-        BuiltinPkgFunctions::GetAndSwitchToRootIFrame => {
+        (BuiltinPkgFunctions::GetAndSwitchToRootIFrame, _) => {
             r#"driver = driver.switchTo().defaultContent(); driver = new Webdriverended(driver)"#
         }
-        BuiltinPkgFunctions::SetWindowDimension => {
+        (BuiltinPkgFunctions::SetWindowDimension, _) => {
             r#"driver.setWindowDimension({arg1}, {arg2})"#
         }
-        BuiltinPkgFunctions::MUIInputTextboxByLabelExact => {
+        // The React MUI getter came with driver 0.2.0
+        (BuiltinPkgFunctions::MUIInputTextboxByLabelExact, DriverVersion::V0_2_0) => {
             r#"reactMUIGetter.getTextboxFromLabel({arg1}).sendText({arg2})"#
         }
-        BuiltinPkgFunctions::MUIInputDateByLabelExact => {
+        (BuiltinPkgFunctions::MUIInputDateByLabelExact, DriverVersion::V0_2_0) => {
             r#"reactMUIGetter.getDateFromLabel({arg1}).sendText({arg2})"#
         }
-        BuiltinPkgFunctions::MUIInputTimeByLabelExact => {
+        (BuiltinPkgFunctions::MUIInputTimeByLabelExact, DriverVersion::V0_2_0) => {
             r#"reactMUIGetter.getTimeFromLabel({arg1}).sendText({arg2})"#
         }
-        BuiltinPkgFunctions::MUIInputHtmlByLabelExact => {
+        (BuiltinPkgFunctions::MUIInputHtmlByLabelExact, DriverVersion::V0_2_0) => {
             r#"reactMUIGetter.getHTMLFromLabel({arg1}).clearText().sendRawText({arg2})"#
         }
-        BuiltinPkgFunctions::MUIInputDropdownUsingTextByLabelExact => {
+        (BuiltinPkgFunctions::MUIInputDropdownUsingTextByLabelExact, DriverVersion::V0_2_0) => {
             r#"reactMUIGetter.getDropdownFromLabel({arg1}).selectElementFromText({arg2})"#
         }
-        BuiltinPkgFunctions::MUIInputDropdownUsingIndexByLabelExact => {
+        (BuiltinPkgFunctions::MUIInputDropdownUsingIndexByLabelExact, DriverVersion::V0_2_0) => {
             r#"reactMUIGetter.getDropdownFromLabel({arg1}).selectElementOnIndex({arg2})"#
         }
-        BuiltinPkgFunctions::MUIInputRadioUsingTextByLabelExact => {
+        (BuiltinPkgFunctions::MUIInputRadioUsingTextByLabelExact, DriverVersion::V0_2_0) => {
             r#"reactMUIGetter.getRadioFromLabel({arg1}).selectElementFromText({arg2})"#
         }
-        BuiltinPkgFunctions::MUIInputRadioUsingIndexByLabelExact => {
+        (BuiltinPkgFunctions::MUIInputRadioUsingIndexByLabelExact, DriverVersion::V0_2_0) => {
             r#"reactMUIGetter.getRadioFromLabel({arg1}).selectElementOnIndex({arg2})"#
         }
+        (BuiltinPkgFunctions::Assert, _) => {
+            r#"WebUI.verifyEqual({arg1}, true, {arg2})"#
+        }
+        (BuiltinPkgFunctions::VerifyElementText, _) => {
+            r#"WebUI.verifyMatch({arg1}.getText(), {arg2}, false, {arg3})"#
+        }
+        (BuiltinPkgFunctions::VerifyElementVisible, _) => {
+            r#"WebUI.verifyEqual({arg1}.isDisplayed(), true, {arg2})"#
+        }
+        (BuiltinPkgFunctions::VerifyUrlContains, _) => {
+            r#"WebUI.verifyMatch(driver.getCurrentUrl(), ".*" + java.util.regex.Pattern.quote({arg1}) + ".*", true, {arg2})"#
+        }
+        (BuiltinPkgFunctions::MUIInputTextboxByLabelExact, DriverVersion::V0_1_1)
+        | (BuiltinPkgFunctions::MUIInputDateByLabelExact, DriverVersion::V0_1_1)
+        | (BuiltinPkgFunctions::MUIInputTimeByLabelExact, DriverVersion::V0_1_1)
+        | (BuiltinPkgFunctions::MUIInputHtmlByLabelExact, DriverVersion::V0_1_1)
+        | (BuiltinPkgFunctions::MUIInputDropdownUsingTextByLabelExact, DriverVersion::V0_1_1)
+        | (BuiltinPkgFunctions::MUIInputDropdownUsingIndexByLabelExact, DriverVersion::V0_1_1)
+        | (BuiltinPkgFunctions::MUIInputRadioUsingTextByLabelExact, DriverVersion::V0_1_1)
+        | (BuiltinPkgFunctions::MUIInputRadioUsingIndexByLabelExact, DriverVersion::V0_1_1) => {
+            bail!("{} is unavailable with driver {}.", builtin_fn, driver)
+        }
         // fn_enum => unimplemented!(
         //     "Function {} from default package is currently unimplemented.",
         //     fn_enum
         // ),
-    }
+    })
 }
 
-pub fn get_default_fn_template<'a>(name: &'a str, pkg: &'a str) -> Result<&'a str, Report> {
+pub fn get_default_fn_template<'a>(
+    name: &'a str,
+    pkg: &'a str,
+    driver: DriverVersion,
+) -> Result<&'a str, Report> {
     // TODO: Remove hardcoded package alias switching
     let pkg = if pkg == "#" { "builtin" } else { pkg };
 
@@ -102,10 +144,80 @@ pub fn get_default_fn_template<'a>(name: &'a str, pkg: &'a str) -> Result<&'a st
         "builtin" => {
             let fn_enum = builtin_package_definition::BuiltinPkgFunctions::from_str(name)?;
 
-            get_katalon_default_fn_metadata(fn_enum)
+            get_katalon_default_fn_metadata(fn_enum, driver)?
         }
         str => bail!("Package {} is currently unimplemented.", str),
     };
 
     Ok(fn_template)
 }
+
+fn get_katalon_element_method_metadata<'a>(method: ElementMethods) -> &'a str {
+    match method {
+        ElementMethods::Click => r#"{self}.click()"#,
+        ElementMethods::SendText => r#"{self}.sendKeys({arg1})"#,
+        ElementMethods::GetText => r#"{self}.getText()"#,
+    }
+}
+
+fn get_katalon_conversion_metadata(
+    method: ConversionMethods,
+    val_type: &str,
+) -> Result<&'static str, Report> {
+    Ok(match (method, val_type) {
+        (ConversionMethods::ToString, "string")
+        | (ConversionMethods::ToNumber, "number")
+        | (ConversionMethods::ToBool, "bool") => r#"{self}"#,
+        // toString() of a BigDecimal may use an exponent
+        (ConversionMethods::ToString, "decimal") => r#"({self} as BigDecimal).toPlainString()"#,
+        (ConversionMethods::ToString, _) => r#"String.valueOf({self})"#,
+        (ConversionMethods::ToNumber, "string") => r#"Integer.parseInt({self})"#,
+        (ConversionMethods::ToNumber, "decimal") => r#"({self} as BigDecimal).intValue()"#,
+        (ConversionMethods::ToBool, "string") => r#"Boolean.parseBoolean({self})"#,
+        (method, val_type) => bail!("{} of {} is currently unimplemented.", method, val_type),
+    })
+}
+
+pub fn get_default_method_template<'a>(name: &'a str, val_type: &'a str) -> Result<&'a str, Report> {
+    match val_type {
+        "element" => Ok(get_katalon_element_method_metadata(ElementMethods::from_str(
+            name,
+        )?)),
+        _ => get_katalon_conversion_metadata(ConversionMethods::from_str(name)?, val_type),
+    }
+}
+
+fn get_katalon_default_enum_metadata<'a>(
+    builtin_enum: BuiltinPkgEnums,
+    member: &str,
+) -> Result<&'a str, Report> {
+    Ok(match builtin_enum {
+        BuiltinPkgEnums::ByOption => r#"ByOption.{member}"#,
+        BuiltinPkgEnums::FailureHandling => match member {
+            "Stop" => r#"FailureHandling.STOP_ON_FAILURE"#,
+            "Continue" => r#"FailureHandling.CONTINUE_ON_FAILURE"#,
+            "Optional" => r#"FailureHandling.OPTIONAL"#,
+            member => bail!("FailureHandling.{} is currently unimplemented.", member),
+        },
+    })
+}
+
+pub fn get_default_enum_template<'a>(
+    name: &'a str,
+    member: &'a str,
+    pkg: &'a str,
+) -> Result<&'a str, Report> {
+    // TODO: Remove hardcoded package alias switching
+    let pkg = if pkg == "#" { "builtin" } else { pkg };
+
+    let enum_template = match pkg {
+        "builtin" => {
+            let enum_kind = builtin_package_definition::BuiltinPkgEnums::from_str(name)?;
+
+            get_katalon_default_enum_metadata(enum_kind, member)?
+        }
+        str => bail!("Package {} is currently unimplemented.", str),
+    };
+
+    Ok(enum_template)
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+/// A generated line and the script line it comes from, both starting at 1
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineMapping {
+    pub generated_line: usize,
+    pub source_line: usize,
+}
+
+/// Line level mapping from the generated code back to the Autalon script
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceMap {
+    /// Name of the script file, if known
+    pub source_file: Option<String>,
+    /// Sorted by generated line. Lines without a mapping are boilerplate or comments.
+    pub mappings: Vec<LineMapping>,
+}
+
+impl SourceMap {
+    /// Script line the generated line comes from
+    pub fn source_line(&self, generated_line: usize) -> Option<usize> {
+        self.mappings
+            .binary_search_by_key(&generated_line, |x| x.generated_line)
+            .ok()
+            .map(|i| self.mappings[i].source_line)
+    }
+
+    /// Rewrite the `File.groovy:87` locations of a stack trace into script locations.
+    /// When `generated_file` is given, only locations in that file are rewritten.
+    pub fn translate_stack_trace(&self, trace: &str, generated_file: Option<&str>) -> String {
+        trace
+            .lines()
+            .map(|x| self.translate_stack_trace_line(x, generated_file))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn translate_stack_trace_line(&self, line: &str, generated_file: Option<&str>) -> String {
+        const EXTENSION: &str = ".groovy:";
+
+        let mut res = String::new();
+        let mut rest = line;
+
+        while let Some(extension_start) = rest.find(EXTENSION) {
+            let file_start = rest[..extension_start]
+                .rfind(|x: char| !(x.is_alphanumeric() || x == '_' || x == '-'))
+                .map_or(0, |x| x + 1);
+            let number_start = extension_start + EXTENSION.len();
+            let number_end = rest[number_start..]
+                .find(|x: char| !x.is_ascii_digit())
+                .map_or(rest.len(), |x| number_start + x);
+
+            let file = &rest[file_start..extension_start + ".groovy".len()];
+            let source_line = rest[number_start..number_end]
+                .parse()
+                .ok()
+                .filter(|_| generated_file.is_none_or(|x| x == file))
+                .and_then(|x| self.source_line(x));
+
+            res.push_str(&rest[..file_start]);
+            match source_line {
+                Some(source_line) => res.push_str(&format!(
+                    "{}:{source_line}",
+                    self.source_file.as_deref().unwrap_or("script.atln")
+                )),
+                None => res.push_str(&rest[file_start..number_end]),
+            }
+
+            rest = &rest[number_end..];
+        }
+
+        res + rest
+    }
+}
+
+/// Generated code and where each of its lines comes from
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transpiled {
+    pub code: String,
+    pub source_map: SourceMap,
+}
@@ -0,0 +1,215 @@
+//! Comments, which the grammar mostly skips like whitespace, recovered from the source text and
+//! attached to the statements around them.
+
+use crate::autalonparser::Rule;
+use pest::iterators::{Pair, Pairs};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CommentKind {
+    /// `// ...`
+    Line,
+    /// `/* ... */`
+    Block,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Comment {
+    pub kind: CommentKind,
+    /// Text of the comment without its delimiters
+    pub content: String,
+    /// Byte range of the comment in the source, delimiters included
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Comment {
+    fn block(code: &str, start: usize, end: usize) -> Comment {
+        let text = &code[start..end];
+
+        Comment {
+            kind: CommentKind::Block,
+            content: text
+                .strip_prefix("/*")
+                .map(|x| x.strip_suffix("*/").unwrap_or(x))
+                .unwrap_or(text)
+                .to_string(),
+            start,
+            end,
+        }
+    }
+
+    /// Comment from an `INLINE_COMMENT` pair
+    pub fn line(pair: &Pair<Rule>) -> Comment {
+        let text = pair.as_str().trim_end();
+        let start = pair.as_span().start();
+
+        Comment {
+            kind: CommentKind::Line,
+            content: text.strip_prefix("//").unwrap_or(text).to_string(),
+            start,
+            end: start + text.len(),
+        }
+    }
+
+    /// The comment as written in the source
+    pub fn source(&self) -> String {
+        match self.kind {
+            CommentKind::Line => format!("//{}", self.content),
+            CommentKind::Block => format!("/*{}*/", self.content),
+        }
+    }
+}
+
+/// Block comments in text that is known to hold only whitespace and comments,
+/// e.g. between two statements
+pub fn gap_comments(code: &str, start: usize, end: usize) -> Vec<Comment> {
+    let mut comments = vec![];
+    let mut offset = start;
+
+    while offset < end {
+        let rest = &code[offset..end];
+        let trimmed = rest.trim_start();
+        let comment_start = offset + rest.len() - trimmed.len();
+
+        if !trimmed.starts_with("/*") {
+            break;
+        }
+
+        let comment_end = trimmed.find("*/").map_or(end, |x| comment_start + x + 2);
+        comments.push(Comment::block(code, comment_start, comment_end));
+
+        offset = comment_end;
+    }
+
+    comments
+}
+
+/// Block comments inside a statement, skipping the content of strings
+pub fn inner_comments(code: &str, start: usize, end: usize) -> Vec<Comment> {
+    let text = &code[start..end];
+    let mut comments = vec![];
+    let mut in_string = false;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if in_string {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => in_string = false,
+                _ => (),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '/' if chars.peek().map(|x| x.1) == Some('*') => {
+                let comment_end = text[i..].find("*/").map_or(text.len(), |x| i + x + 2);
+                comments.push(Comment::block(code, start + i, start + comment_end));
+
+                while chars.peek().is_some_and(|x| x.0 < comment_end) {
+                    chars.next();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    comments
+}
+
+/// Whether the pair is a statement made of an escape block, whose content is kept verbatim
+pub fn is_escape_statement(pair: &Pair<Rule>) -> bool {
+    pair.as_rule() == Rule::statement
+        && pair
+            .clone()
+            .into_inner()
+            .next()
+            .is_some_and(|x| x.as_rule() == Rule::escape_block)
+}
+
+/// Comments around a statement
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Trivia {
+    /// Comments on the lines before the statement, and the ones inside it
+    pub leading: Vec<Comment>,
+    /// Comments after the statement, on the same line
+    pub trailing: Vec<Comment>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnnotatedStatement<'a> {
+    pub pair: Pair<'a, Rule>,
+    pub trivia: Trivia,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AnnotatedProgram<'a> {
+    pub statements: Vec<AnnotatedStatement<'a>>,
+    /// Comments after the last statement
+    pub dangling: Vec<Comment>,
+}
+
+/// A comment on the same line as the previous statement trails it, others lead the next statement
+fn attach(
+    code: &str,
+    program: &mut AnnotatedProgram,
+    pending: &mut Vec<Comment>,
+    comment: Comment,
+) {
+    match program.statements.last_mut() {
+        Some(previous)
+            if pending.is_empty()
+                && !code[previous.pair.as_span().end()..comment.start].contains('\n') =>
+        {
+            previous.trivia.trailing.push(comment)
+        }
+        _ => pending.push(comment),
+    }
+}
+
+/// Attach every comment of a parsed program to the statement it belongs to
+pub fn annotate<'a>(code: &str, pairs: Pairs<'a, Rule>) -> AnnotatedProgram<'a> {
+    let mut program = AnnotatedProgram::default();
+    let mut pending: Vec<Comment> = vec![];
+    let mut last_end = 0;
+
+    for pair in pairs {
+        let span = pair.as_span();
+        for comment in gap_comments(code, last_end, span.start()) {
+            attach(code, &mut program, &mut pending, comment);
+        }
+        last_end = span.end();
+
+        match pair.as_rule() {
+            Rule::EOI => break,
+            Rule::INLINE_COMMENT => attach(code, &mut program, &mut pending, Comment::line(&pair)),
+            Rule::statement => {
+                let mut leading = std::mem::take(&mut pending);
+                if !is_escape_statement(&pair) {
+                    leading.extend(inner_comments(code, span.start(), span.end()));
+                }
+
+                program.statements.push(AnnotatedStatement {
+                    pair,
+                    trivia: Trivia {
+                        leading,
+                        trailing: vec![],
+                    },
+                });
+            }
+            _ => (),
+        }
+    }
+
+    for comment in gap_comments(code, last_end, code.len()) {
+        attach(code, &mut program, &mut pending, comment);
+    }
+    program.dangling = pending;
+
+    program
+}